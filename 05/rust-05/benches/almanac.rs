@@ -0,0 +1,38 @@
+use aoc_common::Rng;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rust_05::generate::almanac;
+use rust_05::parse_almanac;
+
+/** Functions */
+
+fn lookup_chain(c: &mut Criterion) {
+    let mut rng = Rng::new(5);
+    let almanacs = [
+        ("example", parse_almanac(include_str!("../example.txt")).unwrap()),
+        ("input", parse_almanac(include_str!("../input.txt")).unwrap()),
+        ("synthetic_1000", parse_almanac(&almanac(&mut rng, 10, 7, 1000)).unwrap()),
+    ];
+
+    let mut group = c.benchmark_group("day05/location");
+    for (name, almanac) in &almanacs {
+        group.bench_with_input(BenchmarkId::from_parameter(name), almanac, |b, almanac| {
+            b.iter(|| almanac.lowest_location())
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("day05/location_of_ranges");
+    for (name, almanac) in &almanacs {
+        group.bench_with_input(BenchmarkId::from_parameter(name), almanac, |b, almanac| {
+            b.iter(|| almanac.lowest_location_of_ranges())
+        });
+    }
+    group.finish();
+
+    // One map on its own, for a key that misses every entry and has to scan the whole table
+    let almanac = &almanacs[2].1;
+    c.bench_function("day05/lookup_miss_1000", |b| b.iter(|| almanac.maps[0].lookup(black_box(u64::MAX))));
+}
+
+criterion_group!(benches, lookup_chain);
+criterion_main!(benches);
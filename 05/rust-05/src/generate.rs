@@ -0,0 +1,72 @@
+use aoc_common::Rng;
+
+// Random almanacs in the puzzle format. Like the real input, the source ranges of a map
+// never overlap and neither do its destination ranges, so every map is a bijection.
+
+/** Statics */
+
+const CATEGORIES: [&str; 8] = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+
+// Keys live in 0..KEY_SPACE, as in the real input
+const KEY_SPACE: u64 = 1 << 32;
+
+/** Functions */
+
+// Category i of a chain of `maps` maps, inventing names when there are more than the puzzle's seven
+fn category(i: usize, maps: usize) -> String {
+    if i == maps {
+        return String::from("location");
+    }
+    if i < CATEGORIES.len() - 1 {
+        return String::from(CATEGORIES[i]);
+    }
+    // Map headers may not contain digits, so number the extra ones in letters
+    let mut name = String::from("stage-");
+    let mut n = i;
+    loop {
+        name.push((b'a' + (n % 26) as u8) as char);
+        n /= 26;
+        if n == 0 {
+            break;
+        }
+    }
+    name
+}
+
+// `seeds` seed ranges followed by `maps` maps of `entries` entries each
+pub fn almanac(rng: &mut Rng, seeds: usize, maps: usize, entries: usize) -> String {
+    assert!(entries > 0 && maps > 0, "An almanac needs at least one map with one entry");
+    let slot = KEY_SPACE / entries as u64;
+
+    let mut text = String::from("seeds:");
+    for _ in 0..seeds {
+        text += &format!(" {} {}", rng.below(KEY_SPACE), rng.below(slot) + 1);
+    }
+    text += "\n";
+
+    for m in 0..maps {
+        text += &format!("\n{}-to-{} map:\n", category(m, maps), category(m + 1, maps));
+
+        // Entry i takes part of source slot i and part of destination slot targets[i]
+        let mut targets = (0..entries as u64).collect::<Vec<_>>();
+        rng.shuffle(&mut targets);
+        let mut lines = targets
+            .iter()
+            .enumerate()
+            .map(|(i, target)| {
+                let length = rng.below(slot) + 1;
+                let src = i as u64 * slot + rng.below(slot - length + 1);
+                let dst = target * slot + rng.below(slot - length + 1);
+                format!("{} {} {}", dst, src, length)
+            })
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut lines);
+
+        text += &lines.join("\n");
+        text += "\n";
+    }
+
+    // Like the real input, without a newline at the end
+    text.truncate(text.trim_end().len());
+    text
+}
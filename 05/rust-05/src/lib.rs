@@ -0,0 +1,271 @@
+use aoc_common::parse::{self, ParseError, Span};
+use aoc_common::{Answer, AocError, Solution};
+
+pub mod generate;
+
+struct AgroKey {
+    src: u64,
+    length: u64,
+    dst: u64,
+}
+
+pub struct AgroMap {
+    table: Vec<AgroKey>,
+}
+
+impl AgroKey{
+    // "destination source length"
+    fn new(line: &Span) -> Result<AgroKey, ParseError> {
+        let parts = line.words();
+        if parts.len() != 3 {
+            return Err(line.error(format!("expected 'destination source length', found {} numbers", parts.len())));
+        }
+        Ok(AgroKey {
+            src: parts[1].parse::<u64>()?,
+            length: parts[2].parse::<u64>()?,
+            dst: parts[0].parse::<u64>()?,
+        })
+    }
+}
+
+impl AgroMap {
+
+    // Debug helper
+    #[allow(dead_code)]
+    fn description(&self) {
+        for key in &self.table {
+            println!("{} {} {}", key.src, key.length, key.dst);
+        }
+    }
+
+    // Header line followed by one line per entry
+    fn new(section: &[Span]) -> Result<AgroMap, ParseError> {
+        let header = section[0];
+        if !header.text.ends_with("map:") {
+            return Err(header.error(format!("expected a 'x-to-y map:' header, found '{}'", header.text)));
+        }
+        // TODO make sure map is sorted
+        Ok(AgroMap {
+            table: section[1..].iter().map(AgroKey::new).collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+
+    pub fn lookup(&self, key: u64) -> u64 {
+        for k in &self.table {
+            if k.src <= key && key < k.src + k.length {
+                return k.dst + (key - k.src);
+            }
+        }
+        key
+    }
+
+    // Map whole (start, length) ranges of keys, splitting them where they cross the
+    // edges of the table entries
+    pub fn lookup_ranges(&self, ranges: &[(u64, u64)]) -> Vec<(u64, u64)> {
+        let mut pending = ranges.to_vec();
+        let mut mapped = Vec::new();
+
+        while let Some((start, length)) = pending.pop() {
+            let end = start + length;
+            match self.table.iter().find(|k| k.src < end && start < k.src + k.length) {
+                Some(k) => {
+                    let lo = start.max(k.src);
+                    let hi = end.min(k.src + k.length);
+                    mapped.push((k.dst + (lo - k.src), hi - lo));
+                    if start < lo {
+                        pending.push((start, lo - start));
+                    }
+                    if hi < end {
+                        pending.push((hi, end - hi));
+                    }
+                },
+                None => mapped.push((start, length)),
+            }
+        }
+        mapped
+    }
+}
+
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<AgroMap>,
+}
+
+impl Almanac {
+    // Follow a seed through every map
+    pub fn location(&self, seed: u64) -> u64 {
+        let mut key = seed;
+        for m in &self.maps {
+            key = m.lookup(key);
+        }
+        key
+    }
+
+    pub fn lowest_location(&self) -> Option<u64> {
+        self.seeds.iter().map(|&x| self.location(x)).min()
+    }
+
+    // Part 2 reads the seeds as (start, length) pairs
+    pub fn lowest_location_of_ranges(&self) -> Option<u64> {
+        let mut ranges = self.seeds.chunks(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect::<Vec<_>>();
+        for m in &self.maps {
+            ranges = m.lookup_ranges(&ranges);
+        }
+        ranges.iter().map(|r| r.0).min()
+    }
+}
+
+// "seeds: ..." on the first line, then one blank line separated section per map
+pub fn parse_almanac(input: &str) -> Result<Almanac, ParseError> {
+
+    let sections = parse::sections(input);
+    let Some((seed_section, map_sections)) = sections.split_first() else {
+        return Err(ParseError::new(1, 1, "empty almanac"));
+    };
+
+    // A map header right below the seeds would otherwise be read as part of the seed section
+    let seed_line = seed_section[0];
+    if let Some(line) = seed_section.get(1) {
+        return Err(line.error("expected a blank line after the seeds"));
+    }
+    let fields = seed_line.split(':');
+    if fields.len() != 2 || fields[0].text != "seeds" {
+        return Err(seed_line.error("expected 'seeds:' followed by the seed numbers"));
+    }
+    let seeds = fields[1].words().iter()
+        .map(|x| x.parse::<u64>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let maps = map_sections.iter().map(|x| AgroMap::new(x)).collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Almanac { seeds, maps })
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const DEFAULT_INPUT: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = Almanac;
+
+    fn parse(input: &str) -> Result<Almanac, AocError> {
+        Ok(parse_almanac(input)?)
+    }
+
+    fn part1(almanac: &Almanac) -> Result<Answer, AocError> {
+        almanac.lowest_location()
+            .map(Answer::from)
+            .ok_or_else(|| AocError::NoSolution(String::from("the almanac lists no seeds")))
+    }
+
+    fn part2(almanac: &Almanac) -> Result<Answer, AocError> {
+        if !almanac.seeds.len().is_multiple_of(2) {
+            return Err(AocError::InvalidInput(format!("part 2 reads the seeds in pairs, found {} seeds", almanac.seeds.len())));
+        }
+        almanac.lowest_location_of_ranges()
+            .map(Answer::from)
+            .ok_or_else(|| AocError::NoSolution(String::from("the almanac lists no seeds")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Rng;
+
+    // Entries as (destination, source, length), like the lines of the almanac
+    fn agro_map(entries: &[(u64, u64, u64)]) -> AgroMap {
+        AgroMap {
+            table: entries.iter().map(|&(dst, src, length)| AgroKey { src, length, dst }).collect(),
+        }
+    }
+
+    fn sorted(mut ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+        ranges.sort();
+        ranges
+    }
+
+    #[test]
+    fn ranges_inside_and_outside_an_entry() {
+        let map = agro_map(&[(100, 10, 10)]);
+
+        assert_eq!(map.lookup_ranges(&[(12, 3)]), vec![(102, 3)]);
+        assert_eq!(map.lookup_ranges(&[(10, 10)]), vec![(100, 10)]);
+        assert_eq!(map.lookup_ranges(&[(0, 5)]), vec![(0, 5)]);
+        assert_eq!(map.lookup_ranges(&[(30, 5)]), vec![(30, 5)]);
+    }
+
+    #[test]
+    fn ranges_touching_an_entry_stay_unmapped() {
+        let map = agro_map(&[(100, 10, 10)]);
+
+        // Ending right where the entry starts, and starting right after it ends
+        assert_eq!(map.lookup_ranges(&[(5, 5)]), vec![(5, 5)]);
+        assert_eq!(map.lookup_ranges(&[(20, 5)]), vec![(20, 5)]);
+        // One key inside at either end
+        assert_eq!(sorted(map.lookup_ranges(&[(5, 6)])), vec![(5, 5), (100, 1)]);
+        assert_eq!(sorted(map.lookup_ranges(&[(19, 6)])), vec![(20, 5), (109, 1)]);
+    }
+
+    #[test]
+    fn partly_overlapping_ranges_are_split() {
+        let map = agro_map(&[(100, 10, 10), (200, 20, 10)]);
+
+        assert_eq!(sorted(map.lookup_ranges(&[(5, 10)])), vec![(5, 5), (100, 5)]);
+        assert_eq!(sorted(map.lookup_ranges(&[(15, 10)])), vec![(105, 5), (200, 5)]);
+        assert_eq!(sorted(map.lookup_ranges(&[(5, 35)])), vec![(5, 5), (30, 10), (100, 10), (200, 10)]);
+    }
+
+    #[test]
+    fn maps_follow_the_seeds_after_a_blank_line() {
+        let almanac = parse_almanac("seeds: 79 14\n\nseed-to-soil map:\n50 79 2\n").unwrap();
+
+        assert_eq!(almanac.seeds, vec![79, 14]);
+        assert_eq!(almanac.lowest_location(), Some(14));
+        assert_eq!(almanac.lowest_location_of_ranges(), Some(50));
+    }
+
+    #[test]
+    fn errors_point_at_the_bad_line_or_number() {
+        let cases = [
+            ("", 1, 1, "empty almanac"),
+            ("seed: 79 14", 1, 1, "expected 'seeds:' followed by the seed numbers"),
+            ("seeds: 79 x4", 1, 11, "invalid u64 'x4'"),
+            ("seeds: 79 14\nseed-to-soil map:\n50 79 2", 2, 1, "expected a blank line after the seeds"),
+            ("seeds: 79 14\n\nseed-to-soil:\n50 79 2", 3, 1, "expected a 'x-to-y map:' header, found 'seed-to-soil:'"),
+            ("seeds: 79 14\n\nseed-to-soil map:\n50 79", 4, 1, "expected 'destination source length', found 2 numbers"),
+            ("seeds: 79 14\n\nseed-to-soil map:\n50 -79 2", 4, 4, "invalid u64 '-79'"),
+        ];
+        for (input, line, column, message) in cases {
+            assert_eq!(parse_almanac(input).err(), Some(ParseError::new(line, column, message)), "{}", input);
+        }
+    }
+
+    // Every key of every range has to end up where lookup sends it
+    #[test]
+    fn ranges_match_key_by_key_lookup() {
+        let mut rng = Rng::new(5);
+        for _ in 0..200 {
+            let mut entries = Vec::new();
+            let mut src = rng.below(10);
+            for _ in 0..rng.below(5) {
+                let length = rng.below(10) + 1;
+                entries.push((rng.below(100), src, length));
+                src += length + rng.below(5);
+            }
+            let map = agro_map(&entries);
+            let range = (rng.below(60), rng.below(30) + 1);
+
+            let mut expected = (range.0..range.0 + range.1).map(|k| map.lookup(k)).collect::<Vec<_>>();
+            let mut found = map.lookup_ranges(&[range]).iter()
+                .flat_map(|&(start, length)| start..start + length)
+                .collect::<Vec<_>>();
+            expected.sort();
+            found.sort();
+            assert_eq!(found, expected, "{:?} through {:?}", range, entries);
+        }
+    }
+}
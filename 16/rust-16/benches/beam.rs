@@ -0,0 +1,41 @@
+use aoc_common::{Grid, Rng};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rust_16::generate::contraption;
+use rust_16::{beam_walk, count_energized, parse_contraption, part2, Direction, Tile};
+
+/** Functions */
+
+fn contraptions() -> Vec<(String, Grid<Tile>)> {
+    let mut rng = Rng::new(16);
+    vec![
+        (String::from("example"), parse_contraption(include_str!("../src/example.txt")).unwrap()),
+        (String::from("input"), parse_contraption(include_str!("../src/input.txt")).unwrap()),
+        (String::from("synthetic_150"), parse_contraption(&contraption(&mut rng, 150, 150, 0.05)).unwrap()),
+    ]
+}
+
+fn single_entry(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day16/beam_walk");
+    for (name, tiles) in contraptions() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &tiles, |b, tiles| {
+            b.iter(|| {
+                let mut lines = tiles.clone();
+                beam_walk(&mut lines, 0, 0, Direction::RIGHT);
+                count_energized(&lines)
+            })
+        });
+    }
+    group.finish();
+}
+
+fn part2_sweep(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day16/part2");
+    group.sample_size(10);
+    for (name, tiles) in contraptions() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &tiles, |b, tiles| b.iter(|| part2(tiles)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, single_entry, part2_sweep);
+criterion_main!(benches);
@@ -0,0 +1,19 @@
+use aoc_common::Rng;
+
+/** Statics */
+
+const OPTICS: [char; 4] = ['/', '\\', '-', '|'];
+
+/** Functions */
+
+// width x height contraption where each tile is a mirror or splitter with probability `density`
+pub fn contraption(rng: &mut Rng, width: usize, height: usize, density: f64) -> String {
+    let rows = (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| if rng.chance(density) { OPTICS[rng.below(OPTICS.len() as u64) as usize] } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    rows.join("\n")
+}
@@ -0,0 +1,359 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::cmp;
+use aoc_common::parse::{self, ParseError};
+use aoc_common::{Answer, AocError, Grid, Solution};
+
+pub mod generate;
+
+#[derive(Clone)]
+pub struct Tile {
+    tile_type: char,
+    beams: HashMap<Direction, bool>,
+    energized: bool,
+}
+
+impl Tile {
+    fn new(tile_type: char) -> Tile {
+        Tile {
+            tile_type,
+            beams: HashMap::from([
+                (Direction::LEFT, false),
+                (Direction::RIGHT, false),
+                (Direction::UP, false),
+                (Direction::DOWN, false)]
+            ),
+            energized: false,
+        }
+    }
+
+    fn heading_is_set(&self, heading: &Heading) -> bool {
+        match heading {
+            Heading::HORIZONTAL => self.beams[&Direction::LEFT] || self.beams[&Direction::RIGHT],
+            Heading::VERTICAL => self.beams[&Direction::UP] || self.beams[&Direction::DOWN],
+        }
+    }
+}
+
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Direction {
+    LEFT,
+    RIGHT,
+    UP,
+    DOWN
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+enum Heading {
+    HORIZONTAL,
+    VERTICAL,
+}
+
+fn direction_to_heading(direction: &Direction) -> Heading {
+    match direction {
+        Direction::LEFT | Direction::RIGHT => Heading::HORIZONTAL,
+        Direction::UP | Direction::DOWN => Heading::VERTICAL,
+    }
+}
+
+impl Direction {
+    fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::LEFT => (-1, 0),
+            Direction::RIGHT => (1, 0),
+            Direction::UP => (0, -1),
+            Direction::DOWN => (0, 1),
+        }
+    }
+}
+
+// Empty tiles show the direction of a beam passing through them
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self.tile_type {
+            '.' => if self.beams[&Direction::LEFT] {
+                '<'
+            } else if self.beams[&Direction::RIGHT] {
+                '>'
+            } else if self.beams[&Direction::UP] {
+                '^'
+            } else if self.beams[&Direction::DOWN] {
+                'v'
+            } else {
+                self.tile_type
+            },
+            _ => self.tile_type,
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+// Debug helpers
+#[allow(dead_code)]
+fn print_tile(tile: &Tile) {
+    print!("{}", tile.tile_type);
+}
+
+#[allow(dead_code)]
+fn print_tiles(tiles: &Grid<Tile>) {
+    print!("{}", tiles);
+}
+
+#[allow(dead_code)]
+fn print_energized_tiles(tiles: &Grid<Tile>) {
+    print!("{}", tiles.map(|tile| if tile.energized { '#' } else { '.' }));
+}
+
+
+#[allow(dead_code)]
+fn print_type_of<T>(_: &T) {
+    println!("{}", std::any::type_name::<T>())
+}
+
+// Return the next set of coords if they are in bounds
+fn next_tile(tiles: &Grid<Tile>, x: usize, y: usize, direction: &Direction) -> Option<(usize, usize)> {
+    tiles.step(x, y, direction.offset())
+}
+
+pub fn count_energized(tiles: &Grid<Tile>) -> usize {
+    tiles.iter().filter(|tile| tile.energized).count()
+}
+
+// Walks along a beam/ray and splits into recursive calls if a horizontal or vertcal split is encountered.
+// The beam ends when it leaves the grid or runs into a loop.
+pub fn beam_walk(lines: &mut Grid<Tile>, x: usize, y: usize, direction_input: Direction) {
+    let mut x = x;
+    let mut y = y;
+    let mut direction = direction_input;
+
+    loop {
+        let tile = lines.get_mut(x, y).expect("Beam started outside the grid");
+
+        // Detetct if we are in a loop
+        let heading = direction_to_heading(&direction);
+        if tile.heading_is_set(&heading) {
+            break;
+        }
+
+        // print_tile(tile);
+        tile.energized = true;
+        match tile.tile_type {
+            '.' => {
+                tile.beams.insert(direction, true);
+                // Continue loop if we can
+
+            },
+            '/' => {
+                match direction {
+                    Direction::LEFT => {
+                        direction = Direction::DOWN;
+                    },
+                    Direction::RIGHT => {
+                        direction = Direction::UP;
+                    },
+                    Direction::UP => {
+                        direction = Direction::RIGHT;
+                    },
+                    Direction::DOWN => {
+                        direction = Direction::LEFT;
+                    },
+                }
+            }
+            '\\' => {
+                match direction {
+                    Direction::LEFT => {
+                        direction = Direction::UP;
+                    },
+                    Direction::RIGHT => {
+                        direction = Direction::DOWN;
+                    },
+                    Direction::UP => {
+                        direction = Direction::LEFT;
+                    },
+                    Direction::DOWN => {
+                        direction = Direction::RIGHT;
+                    },
+                }
+            }
+            '-' => {
+                match direction {
+                    Direction::UP | Direction::DOWN => {
+                        // Walk left
+                        if let Some((xx, yy)) = next_tile(lines, x, y, &Direction::LEFT) {
+                            beam_walk(lines, xx, yy, Direction::LEFT);
+                        }
+
+                        // Walk right
+                        direction = Direction::RIGHT;
+                    }
+                    _ => (),
+                }
+            },
+            '|' => {
+
+                match direction {
+                    Direction::LEFT | Direction::RIGHT => {
+                        // Walk up
+                        if let Some((xx, yy)) = next_tile(lines, x, y, &Direction::UP) {
+                            beam_walk(lines, xx, yy, Direction::UP);
+                        }
+
+
+                        // Walk down
+                        direction = Direction::DOWN;
+                    }
+                    _ => (),
+                }
+
+            }
+
+            _ => break,
+        }
+
+        match next_tile(lines, x, y, &direction) {
+            Some(next) => (x, y) = next,
+            None => break,
+        }
+    }
+}
+
+// One tile per character: empty space '.', mirrors '/' and '\', splitters '-' and '|'.
+// The beams start at the edges, so there has to be at least one tile.
+pub fn parse_contraption(input: &str) -> Result<Grid<Tile>, ParseError> {
+    for line in parse::lines(input) {
+        if let Some((i, c)) = line.text.chars().enumerate().find(|(_, c)| !".\\/-|".contains(*c)) {
+            return Err(ParseError::new(line.line, i + 1, format!("unknown tile '{}'", c)));
+        }
+    }
+    let tiles = Grid::parse(input, Tile::new)?;
+    if tiles.width() == 0 || tiles.height() == 0 {
+        return Err(ParseError::new(1, 1, "contraption has no tiles"));
+    }
+    Ok(tiles)
+}
+
+// Beam entering the top left corner heading right
+pub fn part1(tiles: &Grid<Tile>) -> usize {
+
+    let mut lines = tiles.clone();
+
+    // print_type_of(&lines);
+    // print_tiles(&lines);
+
+    beam_walk(&mut lines, 0, 0, Direction::RIGHT);
+
+    // print_tiles(&lines);
+    // print_energized_tiles(&lines);
+
+    count_energized(&lines)
+}
+
+pub fn part2(tiles: &Grid<Tile>) -> usize {
+    let lines = tiles;
+
+    // print_type_of(&lines);
+    // print_tiles(&lines);
+
+    let (width, height) = (lines.width(), lines.height());
+
+    let column_zero: Vec<usize> = vec![0; height];
+    let column_max: Vec<usize> = vec![width-1; height];
+    let row_zero: Vec<usize> = vec![0; width];
+    let row_max: Vec<usize> = vec![height-1; width];
+    let x_inc: Vec<usize> = (0..width).collect();
+    let y_inc: Vec<usize> = (0..height).collect();
+
+    let start_vecors = [
+        (
+            Direction::LEFT, // direction
+            column_zero.iter().zip(y_inc.iter()) // start coordinates
+        ),
+        (
+            Direction::RIGHT, // direction
+            column_max.iter().zip(y_inc.iter()) // start coordinates
+        ),
+        (
+            Direction::UP, // direction
+            x_inc.iter().zip(row_max.iter()) // start coordinates
+        ),
+        (
+            Direction::DOWN, // direction
+            x_inc.iter().zip(row_zero.iter()) // start coordinates
+        ),
+    ];
+
+    let mut hightest_energized = 0;
+    for (direction, start_coords) in start_vecors{
+        for (x, y) in start_coords{
+
+            let mut local_lines = tiles.clone();
+
+            beam_walk(&mut local_lines, *x, *y, direction);
+        
+            // Count energized tiles
+            let energized_tiles = count_energized(&local_lines);
+            hightest_energized = cmp::max(hightest_energized, energized_tiles);
+
+            //print_tiles(&local_lines);
+            //print_energized_tiles(&local_lines);
+            // println!("{:?}, {}, {}: {}", direction, *x, *y, energized_tiles);
+        }
+    }
+
+    hightest_energized
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const DEFAULT_INPUT: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+    type Input = Grid<Tile>;
+
+    fn parse(input: &str) -> Result<Grid<Tile>, AocError> {
+        Ok(parse_contraption(input)?)
+    }
+
+    fn part1(tiles: &Grid<Tile>) -> Result<Answer, AocError> {
+        Ok(part1(tiles).into())
+    }
+
+    fn part2(tiles: &Grid<Tile>) -> Result<Answer, AocError> {
+        Ok(part2(tiles).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_energizes_46_tiles_and_at_most_51() {
+        let tiles = parse_contraption(include_str!("example.txt")).unwrap();
+
+        assert_eq!(part1(&tiles), 46);
+        assert_eq!(part2(&tiles), 51);
+    }
+
+    #[test]
+    fn errors_point_at_the_bad_tile_or_row() {
+        let cases = [
+            ("..|.\n.#..", 2, 2, "unknown tile '#'"),
+            ("....\n...\n....", 2, 4, "row 2 has 3 cells, expected 4"),
+            ("..\n...", 2, 3, "row 2 has 3 cells, expected 2"),
+        ];
+        for (input, line, column, message) in cases {
+            assert_eq!(parse_contraption(input).err(), Some(ParseError::new(line, column, message)), "{}", input);
+        }
+    }
+
+    #[test]
+    fn empty_contraption_is_a_parse_error() {
+        for input in ["", "\n", "  \n\n"] {
+            let error = parse_contraption(input).err().unwrap();
+            assert_eq!((error.line, error.column), (1, 1));
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use aoc_common::Rng;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rust_18::area::AreaStrategy;
+use rust_18::generate::dig_plan;
+use rust_18::{dig_trench, DigPlan};
+
+/** Functions */
+
+// Example, input and a synthetic plan over `columns` columns, with the instructions of `part`
+fn plans(part: u8, columns: usize, extent: i64) -> Vec<(String, Vec<(String, i64)>)> {
+    let synthetic = dig_plan(&mut Rng::new(18), columns, extent);
+    [
+        (String::from("example"), include_str!("../example.txt")),
+        (String::from("input"), include_str!("../input.txt")),
+        (format!("synthetic_{}", columns), synthetic.as_str()),
+    ]
+    .into_iter()
+    .map(|(name, text)| (name, DigPlan::parse(text).unwrap().part(part).clone()))
+    .collect()
+}
+
+fn polygon_area(c: &mut Criterion) {
+    let inputs = plans(2, 10000, 2);
+
+    let mut group = c.benchmark_group("day18/polygon_area");
+    for (name, instructions) in &inputs {
+        for strategy in [AreaStrategy::Shoelace, AreaStrategy::Trapezoid] {
+            let id = BenchmarkId::new(format!("{:?}", strategy), name);
+            group.bench_with_input(id, instructions, |b, instructions| b.iter(|| strategy.twice_signed_area(instructions)));
+        }
+    }
+    group.finish();
+}
+
+fn flood_fill(c: &mut Criterion) {
+    let inputs = plans(1, 100, 100);
+
+    let mut group = c.benchmark_group("day18/flood_fill");
+    for (name, instructions) in &inputs {
+        let trench = dig_trench(instructions);
+        group.bench_with_input(BenchmarkId::from_parameter(name), &trench, |b, trench| {
+            b.iter_batched(
+                || trench.clone(),
+                |mut canvas| {
+                    let interior_point = canvas.find_interior_point().unwrap();
+                    canvas.scanline_fill(&interior_point);
+                    canvas.count_ones()
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, polygon_area, flood_fill);
+criterion_main!(benches);
@@ -0,0 +1,201 @@
+use clap::ValueEnum;
+
+use crate::{add, instructions_to_vector, lattice_points, mul, Point, DIRECTIONS};
+
+// Both implementations return twice the signed area of the polygon traced through the
+// centers of the trench cells, positive for counter clockwise plans. A is the area in Pick's
+// theorem A = i + b/2 - 1, where b is the number of trench cells and i the number of cells
+// strictly inside the trench. Diagonal edges only put a trench cell on the lattice points
+// they pass through, so b is summed from the gcd of each edge vector rather than from the
+// dig lengths, and A can be a half integer.
+//
+// The lagoon (interior plus trench) then holds i + b = (2A + b)/2 + 1 cells, and the
+// interior alone holds i = (2A - b)/2 + 1 cells. Both numerators are even, so halving
+// once at the end is exact.
+
+/** Structs */
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum AreaStrategy {
+    /// Shoelace formula over the polygon vertices
+    Shoelace,
+    /// Sum of signed trapezoids under the horizontal edges
+    Trapezoid,
+}
+
+impl AreaStrategy {
+    pub fn twice_signed_area(&self, instructions: &Vec<(String, i64)>) -> i64 {
+        match self {
+            AreaStrategy::Shoelace => polyomino_area_from_instructions(instructions),
+            AreaStrategy::Trapezoid => polyomino_area_from_instructions_2(instructions),
+        }
+    }
+}
+
+/** Functions */
+
+// Number of trench cells, b in Pick's theorem
+pub fn boundary_length(instructions: &[(String, i64)]) -> i64 {
+    instructions.iter()
+        .map(|i| lattice_points(&instructions_to_vector(&i.0, i.1)))
+        .sum()
+}
+
+// Cells in the interior plus the trench itself
+pub fn lagoon_area(instructions: &Vec<(String, i64)>, strategy: AreaStrategy) -> i64 {
+    (strategy.twice_signed_area(instructions).abs() + boundary_length(instructions)) / 2 + 1
+}
+
+// Implementation #1 of polymino area calculation
+fn polyomino_area_from_instructions(instructions: &Vec<(String, i64)>) -> i64 {
+
+    let mut point = Point{x: 0, y: 0};
+    let mut area: i64 = 0;
+
+    for (direction, distance) in instructions{
+        let direction_vector = DIRECTIONS.get(direction).unwrap();
+        let relative_movement = mul(direction_vector, *distance);
+        let x_n = add(&point, &relative_movement);
+        area += point.x * x_n.y - x_n.x * point.y;
+
+        point = x_n;
+    }
+
+    area
+}
+
+// Implementation #2 of polymino area calculation.
+// Sums the trapezoids between each edge and the x axis, twice over so diagonal
+// edges with a half cell area stay in integers. Moving right above the axis adds
+// area under a clockwise plan, hence the sign flip at the end.
+fn polyomino_area_from_instructions_2(instructions: &Vec<(String, i64)>) -> i64 {
+
+    let mut area = 0;
+    let mut y = 0;
+
+    for (direction, distance) in instructions{
+        let dx = DIRECTIONS.get(direction).unwrap().x * distance;
+        let dy = DIRECTIONS.get(direction).unwrap().y * distance;
+
+        area += (2 * y + dy) * dx;
+        y += dy;
+    }
+
+    -area
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Rng;
+    use crate::{read_dig_plan, DigPlan};
+
+    const STRATEGIES: [AreaStrategy; 2] = [AreaStrategy::Shoelace, AreaStrategy::Trapezoid];
+
+    fn example() -> DigPlan {
+        read_dig_plan("example.txt").unwrap()
+    }
+
+    // Closed rectilinear path alternating horizontal and vertical moves
+    fn random_rectilinear(rng: &mut Rng, moves: usize) -> Vec<(String, i64)> {
+        let mut next = || rng.range(-10, 11);
+
+        let (mut x, mut y) = (0, 0);
+        let mut instructions = Vec::new();
+        let push = |dx: i64, dy: i64, instructions: &mut Vec<(String, i64)>| {
+            let direction = match (dx.signum(), dy.signum()) {
+                (1, _) => "R",
+                (-1, _) => "L",
+                (_, 1) => "U",
+                _ => "D",
+            };
+            instructions.push((String::from(direction), dx.abs() + dy.abs()));
+        };
+
+        for _ in 0..moves {
+            let dx = next();
+            let dy = next();
+            push(dx, 0, &mut instructions);
+            push(0, dy, &mut instructions);
+            x += dx;
+            y += dy;
+        }
+        push(-x, 0, &mut instructions);
+        push(0, -y, &mut instructions);
+
+        instructions
+    }
+
+    #[test]
+    fn strategies_agree_on_example() {
+        let plan = example();
+        for instructions in [plan.instructions, plan.hex_instructions] {
+            assert_eq!(
+                AreaStrategy::Shoelace.twice_signed_area(&instructions),
+                AreaStrategy::Trapezoid.twice_signed_area(&instructions)
+            );
+        }
+    }
+
+    #[test]
+    fn strategies_agree_on_random_rectilinear_polygons() {
+        let mut rng = Rng::new(18);
+        for moves in 1..200 {
+            let instructions = random_rectilinear(&mut rng, moves);
+            assert_eq!(
+                AreaStrategy::Shoelace.twice_signed_area(&instructions),
+                AreaStrategy::Trapezoid.twice_signed_area(&instructions),
+                "{:?}",
+                instructions
+            );
+        }
+    }
+
+    #[test]
+    fn diagonal_plan_counts_lattice_points() {
+        // Diamond with corners (0, 0), (2, 2), (0, 4) and (-2, 2), holding 1 + 3 + 5 + 3 + 1 cells
+        let instructions = ["UR", "UL", "DL", "DR"].iter()
+            .map(|d| (String::from(*d), 2))
+            .collect::<Vec<_>>();
+
+        assert_eq!(boundary_length(&instructions), 8);
+        for strategy in STRATEGIES {
+            assert_eq!(strategy.twice_signed_area(&instructions), 16);
+            assert_eq!(lagoon_area(&instructions, strategy), 13);
+        }
+    }
+
+    #[test]
+    fn diagonal_plan_with_half_cell_area() {
+        // Triangle (0, 0), (1, 0), (0, 1) of area 1/2, every one of its three cells on the trench
+        let instructions = [("R", 1), ("UL", 1), ("D", 1)].iter()
+            .map(|(d, l)| (String::from(*d), *l))
+            .collect::<Vec<_>>();
+
+        assert_eq!(boundary_length(&instructions), 3);
+        for strategy in STRATEGIES {
+            assert_eq!(strategy.twice_signed_area(&instructions), 1);
+            assert_eq!(lagoon_area(&instructions, strategy), 3);
+        }
+
+        // Same triangle the other way round
+        let reversed = [("U", 1), ("DR", 1), ("L", 1)].iter()
+            .map(|(d, l)| (String::from(*d), *l))
+            .collect::<Vec<_>>();
+        for strategy in STRATEGIES {
+            assert_eq!(strategy.twice_signed_area(&reversed), -1);
+            assert_eq!(lagoon_area(&reversed, strategy), 3);
+        }
+    }
+
+    #[test]
+    fn lagoon_and_interior_area_of_example() {
+        let DigPlan { instructions: part1, hex_instructions: part2, .. } = example();
+        for strategy in STRATEGIES {
+            assert_eq!(lagoon_area(&part1, strategy), 62);
+            let interior = (strategy.twice_signed_area(&part1).abs() - boundary_length(&part1)) / 2 + 1;
+            assert_eq!(interior, 62 - boundary_length(&part1));
+            assert_eq!(lagoon_area(&part2, strategy), 952408144115);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use aoc_common::grid::{neighbors8, step};
+
+use crate::Point;
+
+/** Structs */
+
+// One bit per cell, each row padded to a whole number of u64 words
+#[derive(Clone)]
+pub struct Bitmap {
+    pub width: usize,
+    pub height: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl Bitmap {
+    pub fn new(width: usize, height: usize) -> Bitmap {
+        let words_per_row = width.div_ceil(64);
+        Bitmap {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height],
+        }
+    }
+
+    fn index(&self, x: usize, y: usize) -> (usize, u64) {
+        (y * self.words_per_row + x / 64, 1 << (x % 64))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let (word, mask) = self.index(x, y);
+        self.words[word] & mask != 0
+    }
+
+    pub fn set(&mut self, x: usize, y: usize) {
+        let (word, mask) = self.index(x, y);
+        self.words[word] |= mask;
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    // Scanline flood fill with 4-connectivity, starting from an unset cell.
+    // Each popped seed is widened to the full free span of its row, and the rows
+    // above and below get one new seed per free run under that span.
+    pub fn scanline_fill(&mut self, start: &Point) {
+        let mut seeds = vec![(start.x as usize, start.y as usize)];
+
+        while let Some((x, y)) = seeds.pop() {
+            if self.get(x, y) {
+                continue;
+            }
+
+            let mut left = x;
+            while left > 0 && !self.get(left - 1, y) {
+                left -= 1;
+            }
+            let mut right = x;
+            while right + 1 < self.width && !self.get(right + 1, y) {
+                right += 1;
+            }
+
+            for xx in left..=right {
+                self.set(xx, y);
+            }
+
+            let neighbour_rows = [(0, -1), (0, 1)]
+                .into_iter()
+                .filter_map(|d| step(left, y, d, self.width, self.height));
+            for (_, yy) in neighbour_rows {
+                let mut in_run = false;
+                for xx in left..=right {
+                    let free = !self.get(xx, yy);
+                    if free && !in_run {
+                        seeds.push((xx, yy));
+                    }
+                    in_run = free;
+                }
+            }
+        }
+    }
+
+    // A free cell just right of the leftmost trench cell of a row, where the trench passes
+    // through that cell from the row below to the row above. That cell is then on the left
+    // boundary of the lagoon, for straight and diagonal trenches alike.
+    pub fn find_interior_point(&self) -> Option<Point> {
+        for y in 0..self.height {
+            if let Some(x) = (0..self.width).find(|x| self.get(*x, y)) {
+                let trench = |(xx, yy): &(usize, usize)| self.get(*xx, *yy);
+                let above = neighbors8(x, y, self.width, self.height).filter(trench).any(|(_, yy)| yy > y);
+                let below = neighbors8(x, y, self.width, self.height).filter(trench).any(|(_, yy)| yy < y);
+                let right = step(x, y, (1, 0), self.width, self.height).filter(|p| !trench(p));
+                if let Some((xx, _)) = right.filter(|_| above && below) {
+                    return Some(Point { x: xx as i64, y: y as i64 });
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Bitmap with a set cell for every '#', row i at y = i
+    fn bitmap(rows: &[&str]) -> Bitmap {
+        let mut bitmap = Bitmap::new(rows[0].len(), rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, _) in row.chars().enumerate().filter(|(_, c)| *c == '#') {
+                bitmap.set(x, y);
+            }
+        }
+        bitmap
+    }
+
+    #[test]
+    fn fill_reaches_every_branch() {
+        // The row below the start splits into two runs, each needs its own seed
+        let mut canvas = bitmap(&[
+            "#####.",
+            "#...#.",
+            "#.#.#.",
+            "#####.",
+        ]);
+        canvas.scanline_fill(&Point { x: 1, y: 1 });
+
+        assert_eq!(canvas.count_ones(), 20);
+        assert!((0..4).all(|y| !canvas.get(5, y)));
+    }
+
+    #[test]
+    fn interior_point_of_straight_and_diagonal_trenches() {
+        let square = bitmap(&[
+            "....",
+            "####",
+            "#..#",
+            "####",
+        ]);
+        assert_eq!(square.find_interior_point(), Some(Point { x: 1, y: 2 }));
+
+        let mut diamond = bitmap(&[
+            "..#..",
+            ".#.#.",
+            "#...#",
+            ".#.#.",
+            "..#..",
+        ]);
+        let start = diamond.find_interior_point().unwrap();
+        assert_eq!(start, Point { x: 2, y: 1 });
+        diamond.scanline_fill(&start);
+        assert_eq!(diamond.count_ones(), 13);
+    }
+
+    #[test]
+    fn no_interior_point_without_interior_cells() {
+        assert_eq!(bitmap(&["##", "##"]).find_interior_point(), None);
+        assert_eq!(bitmap(&["#.", "##"]).find_interior_point(), None);
+    }
+}
@@ -0,0 +1,102 @@
+use aoc_common::Rng;
+
+// Random dig plans in the puzzle format. Each plan traces a band over a row of columns: the
+// bottom edge runs right at a random depth below y = 0 in every column, and the top edge comes
+// back left at a random height above it. The two edges stay on their own side of y = 0, so the
+// trench is always closed and never crosses itself.
+//
+// The hex codes hold a second band with the same number of moves and much longer edges, so
+// part 2 gets a valid plan of its own.
+
+/** Statics */
+
+// Part 2 lengths are five hex digits, and a vertical move can span a depth plus a height
+const PART2_EXTENT: i64 = 0xfffff / 2;
+
+/** Functions */
+
+// Value in 1..=extent that differs from `previous`
+fn level(rng: &mut Rng, extent: i64, previous: i64) -> i64 {
+    loop {
+        let value = rng.range(1, extent + 1);
+        if value != previous {
+            return value;
+        }
+    }
+}
+
+// 4 * columns + 1 moves around a band of the given number of columns
+fn band(rng: &mut Rng, columns: usize, extent: i64) -> Vec<(&'static str, i64)> {
+    let vertical = |from: i64, to: i64| (if to > from { "U" } else { "D" }, (to - from).abs());
+    let widths = (0..columns).map(|_| rng.range(1, extent + 1)).collect::<Vec<_>>();
+
+    let mut moves = Vec::with_capacity(4 * columns + 1);
+    let mut y = 0;
+    for width in &widths {
+        let depth = -level(rng, extent, -y);
+        moves.push(vertical(y, depth));
+        moves.push(("R", *width));
+        y = depth;
+    }
+
+    let mut heights = Vec::with_capacity(columns);
+    for _ in 0..columns {
+        let previous = heights.last().copied().unwrap_or(0);
+        heights.push(level(rng, extent, previous));
+    }
+    for (width, height) in widths.iter().zip(&heights).rev() {
+        moves.push(vertical(y, *height));
+        moves.push(("L", *width));
+        y = *height;
+    }
+    moves.push(vertical(y, 0));
+
+    moves
+}
+
+// Plan over `columns` columns, with part 1 edges of at most `extent` cells (twice that for
+// a vertical edge joining the bottom and the top)
+pub fn dig_plan(rng: &mut Rng, columns: usize, extent: i64) -> String {
+    assert!(columns > 0 && extent > 1, "A dig plan needs a column and room to vary its edges");
+    let part1 = band(rng, columns, extent);
+    let part2 = band(rng, columns, PART2_EXTENT);
+
+    let lines = part1
+        .iter()
+        .zip(&part2)
+        .map(|((direction, length), (direction_2, length_2))| {
+            let digit = match *direction_2 {
+                "R" => 0,
+                "D" => 1,
+                "L" => 2,
+                _ => 3,
+            };
+            format!("{} {} (#{:05x}{})", direction, length, length_2, digit)
+        })
+        .collect::<Vec<_>>();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::area::{lagoon_area, AreaStrategy};
+    use crate::polygon::Polygon;
+    use crate::{instructions_to_vector, part1, DigPlan};
+
+    #[test]
+    fn generated_plans_are_valid_and_fill_agrees_with_area() {
+        let mut rng = Rng::new(18);
+        for columns in [1, 2, 5, 20] {
+            let text = dig_plan(&mut rng, columns, 12);
+            let plan = DigPlan::parse(&text).unwrap();
+
+            for instructions in [&plan.instructions, &plan.hex_instructions] {
+                let vectors = instructions.iter().map(|i| instructions_to_vector(&i.0, i.1)).collect::<Vec<_>>();
+                assert!(Polygon::from_vectors(&vectors).validate().is_ok(), "{}", text);
+            }
+
+            assert_eq!(part1(&plan).unwrap() as i64, lagoon_area(&plan.instructions, AreaStrategy::Shoelace), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use aoc_common::parse::{self, ParseError, Span};
+use aoc_common::{read_input, Answer, AocError, Solution};
+use phf::phf_map;
+
+pub mod area;
+pub mod bitmap;
+pub mod generate;
+pub mod polygon;
+pub mod render;
+use area::{lagoon_area, AreaStrategy};
+use bitmap::Bitmap;
+use polygon::Polygon;
+use render::{parse_color, Color};
+
+/** Structs */
+
+// Dig plan read once, with the instructions of both parts side by side
+#[derive(Debug, Clone)]
+pub struct DigPlan {
+    // Directions and lengths as written, for part 1
+    pub instructions: Vec<(String, i64)>,
+    // Directions and lengths hidden in the colors, for part 2
+    pub hex_instructions: Vec<(String, i64)>,
+    pub colors: Vec<Color>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl PartialEq for Point {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+#[derive(Debug)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Vector {
+    const fn new(x: i64, y: i64) -> Vector {
+        Vector {x, y}
+    }
+}
+
+
+/** Statics */
+
+static DIRECTIONS: phf::Map<&'static str, Vector> = phf_map! {
+    "U" => Vector::new(0, 1),
+    "D" => Vector::new(0, -1),
+    "L" => Vector::new(-1, 0),
+    "R" => Vector::new(1, 0),
+    "UR" => Vector::new(1, 1),
+    "UL" => Vector::new(-1, 1),
+    "DR" => Vector::new(1, -1),
+    "DL" => Vector::new(-1, -1),
+};
+
+/** Integer math functions */
+fn mul(v1: &Vector, s: i64) -> Vector {
+    Vector::new(v1.x * s, v1.y * s)
+}
+
+fn add(p : &Point, v: &Vector) -> Point {
+    Point{x: p.x + v.x, y: p.y + v.y}
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+// Number of lattice points on a segment along v, counting only one of its endpoints
+fn lattice_points(v: &Vector) -> i64 {
+    gcd(v.x, v.y)
+}
+
+/** Functions */
+
+pub fn read_dig_plan(filename: &str) -> Result<DigPlan, AocError>{
+    Ok(DigPlan::parse(&read_input(filename)?).map_err(|e| e.in_file(filename))?)
+}
+
+// "(#70c710)": the color, and the part 2 instruction hidden in it, five hex digits of
+// length followed by a direction digit
+fn color_word(word: &Span) -> Result<(Color, (String, i64)), ParseError> {
+    let color = parse_color(word)?;
+    let direction = match &word.text[7..8] {
+        "0" => "R",
+        "1" => "D",
+        "2" => "L",
+        "3" => "U",
+        digit => return Err(word.error(format!("expected a direction digit from 0 to 3, found '{}'", digit))),
+    };
+    let value = (i64::from(color.r) << 16) | (i64::from(color.g) << 8) | i64::from(color.b);
+    if value >> 4 == 0 {
+        return Err(word.error("expected a positive length in the color, found 0"));
+    }
+
+    Ok((color, (String::from(direction), value >> 4)))
+}
+
+impl DigPlan {
+    // One "<direction> <length> (#<color>)" line per instruction
+    pub fn parse(input: &str) -> Result<DigPlan, ParseError> {
+        let mut plan = DigPlan { instructions: Vec::new(), hex_instructions: Vec::new(), colors: Vec::new() };
+
+        for line in parse::lines(input) {
+            let words = line.words();
+            let [direction, length, color] = words[..] else {
+                return Err(line.error(format!("expected 'direction length (#color)', found {} words", words.len())));
+            };
+            if !DIRECTIONS.contains_key(direction.text) {
+                return Err(direction.error(format!("unknown direction '{}'", direction.text)));
+            }
+            // A move of zero or fewer cells digs nothing, yet would still show up as an edge
+            let steps = length.parse::<i64>()?;
+            if steps <= 0 {
+                return Err(length.error(format!("expected a positive length, found {}", steps)));
+            }
+            let (color, hex_instruction) = color_word(&color)?;
+
+            plan.instructions.push((String::from(direction.text), steps));
+            plan.hex_instructions.push(hex_instruction);
+            plan.colors.push(color);
+        }
+        if plan.instructions.is_empty() {
+            return Err(ParseError::new(1, 1, "dig plan has no instructions"));
+        }
+        Ok(plan)
+    }
+
+    // Instructions of part 1 or 2
+    pub fn part(&self, part: u8) -> &Vec<(String, i64)> {
+        if part == 2 { &self.hex_instructions } else { &self.instructions }
+    }
+}
+
+pub fn instructions_to_vector(direction: &str, length: i64) -> Vector{
+    let direction = DIRECTIONS.get(direction).unwrap();
+
+    mul(direction, length)
+}
+
+fn polygon_limits(polygon: &[Point]) -> (Point, Point) {
+    let min_x = polygon.iter().min_by_key(|p| p.x).unwrap().x;
+    let max_x = polygon.iter().max_by_key(|p| p.x).unwrap().x;
+    let min_y = polygon.iter().min_by_key(|p| p.y).unwrap().y;
+    let max_y = polygon.iter().max_by_key(|p| p.y).unwrap().y;
+
+    (Point{x: min_x, y: min_y}, Point{x: max_x, y: max_y})
+}
+
+// Trench cells of a dig plan, on a canvas just large enough to hold them
+pub fn dig_trench(instructions: &[(String, i64)]) -> Bitmap {
+    let vectors = instructions.iter()
+        .map(|i| instructions_to_vector(&i.0, i.1))
+        .collect::<Vec<_>>();
+    let polygon = Polygon::from_vectors(&vectors);
+
+    // Fill out canvas
+    let (min, max) = polygon_limits(&polygon.vertices);
+    let mut canvas = Bitmap::new((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize);
+
+    // Draw polygon
+    let mut coords = Point{x: -min.x, y: -min.y};
+    for instruction in instructions {
+        let direction = &instruction.0;
+        let magnitude = instruction.1;
+        
+        let step = DIRECTIONS.get(direction).unwrap();
+        for _ in 0..magnitude {
+            coords = add(&coords, step);
+            // println!("coords: {:?}", coords);
+            canvas.set(coords.x as usize, coords.y as usize);
+        }
+    }
+
+    canvas
+}
+
+/** Main */
+
+pub fn part1(plan: &DigPlan) -> Result<usize, AocError> {
+
+    let instructions = &plan.instructions;
+    let vectors = instructions.iter()
+    .map(|i| instructions_to_vector(&i.0, i.1))
+    .collect::<Vec<_>>();
+
+    // Build polygon
+    let polygon = Polygon::from_vectors(&vectors);
+    if let Err(e) = polygon.validate() {
+        return Err(AocError::InvalidInput(e.to_string()));
+    }
+
+    let mut canvas = dig_trench(instructions);
+
+    // Scanline flood fill from a point just inside the trench. A trench without any cells
+    // inside it, like a 2x2 square, is the whole lagoon.
+    if let Some(interior_point) = canvas.find_interior_point() {
+        canvas.scanline_fill(&interior_point);
+    }
+
+    // Count size of lagoon
+    Ok(canvas.count_ones())
+}
+
+
+pub fn part2(plan: &DigPlan, strategy: AreaStrategy) -> Result<i64, AocError> {
+
+    let instructions = &plan.hex_instructions;
+    let vectors = instructions.iter()
+        .map(|i| instructions_to_vector(&i.0, i.1))
+        .collect::<Vec<_>>();
+
+    // Build polygon
+    let polygon = Polygon::from_vectors(&vectors);
+    if let Err(e) = polygon.validate() {
+        return Err(AocError::InvalidInput(e.to_string()));
+    }
+
+    Ok(lagoon_area(instructions, strategy))
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    const DEFAULT_INPUT: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = DigPlan;
+
+    fn parse(input: &str) -> Result<DigPlan, AocError> {
+        Ok(DigPlan::parse(input)?)
+    }
+
+    fn part1(plan: &DigPlan) -> Result<Answer, AocError> {
+        Ok(part1(plan)?.into())
+    }
+
+    fn part2(plan: &DigPlan) -> Result<Answer, AocError> {
+        Ok(part2(plan, AreaStrategy::Shoelace)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_parts_are_read_in_one_pass() {
+        let plan = DigPlan::parse("R 6 (#70c710)\nUL  2 (#0dc571)\n").unwrap();
+
+        assert_eq!(plan.instructions, vec![(String::from("R"), 6), (String::from("UL"), 2)]);
+        assert_eq!(plan.hex_instructions, vec![(String::from("R"), 461937), (String::from("D"), 56407)]);
+        assert_eq!(plan.colors, vec![Color::new(0x70, 0xc7, 0x10), Color::new(0x0d, 0xc5, 0x71)]);
+    }
+
+    #[test]
+    fn lagoon_without_interior_cells() {
+        // A 2x2 square and the three cell triangle of area.rs, all of their cells on the trench
+        let plans = [
+            "R 1 (#000010)\nU 1 (#000013)\nL 1 (#000012)\nD 1 (#000011)",
+            "R 1 (#000010)\nUL 1 (#000013)\nD 1 (#000011)",
+        ];
+        for input in plans {
+            let plan = DigPlan::parse(input).unwrap();
+            let area = lagoon_area(&plan.instructions, AreaStrategy::Shoelace);
+            assert_eq!(part1(&plan).unwrap() as i64, area, "{}", input);
+        }
+    }
+
+    #[test]
+    fn errors_point_at_the_bad_word() {
+        let cases = [
+            ("R 6 (#70c710)\nX 6 (#70c710)", 2, 1, "unknown direction 'X'"),
+            ("R six (#70c710)", 1, 3, "invalid i64 'six'"),
+            ("R 6  (#70c714)", 1, 6, "expected a direction digit from 0 to 3, found '4'"),
+            ("R 6", 1, 1, "expected 'direction length (#color)', found 2 words"),
+            ("R 6 (#70c710)\nL -3 (#70c710)", 2, 3, "expected a positive length, found -3"),
+            ("U 0 (#70c710)", 1, 3, "expected a positive length, found 0"),
+            ("R 6 (#000002)", 1, 5, "expected a positive length in the color, found 0"),
+            ("\n", 1, 1, "dig plan has no instructions"),
+        ];
+        for (input, line, column, message) in cases {
+            assert_eq!(DigPlan::parse(input).err(), Some(ParseError::new(line, column, message)), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use std::fmt;
+
+use crate::{add, Point, Vector};
+
+/** Structs */
+
+#[derive(Debug)]
+pub struct Polygon {
+    // vertices[i] -> vertices[i+1] is the edge dug by instruction i
+    pub vertices: Vec<Point>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    Degenerate,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PolygonError {
+    NotClosed { start: Point, end: Point },
+    SelfIntersecting(Vec<(usize, usize)>),
+}
+
+impl fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PolygonError::NotClosed { start, end } => write!(
+                f,
+                "dig plan ends at ({}, {}) instead of returning to ({}, {})",
+                end.x, end.y, start.x, start.y
+            ),
+            PolygonError::SelfIntersecting(edges) => {
+                write!(f, "dig plan crosses itself at edges")?;
+                for (a, b) in edges {
+                    write!(f, " ({}, {})", a, b)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/** Segment math */
+
+// Z component of (a - o) x (b - o)
+fn cross(o: &Point, a: &Point, b: &Point) -> i64 {
+    (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+}
+
+// Assumes p, q, r are collinear; checks whether q lies within the bounding box of p-r
+fn on_segment(p: &Point, q: &Point, r: &Point) -> bool {
+    q.x >= p.x.min(r.x) && q.x <= p.x.max(r.x) && q.y >= p.y.min(r.y) && q.y <= p.y.max(r.y)
+}
+
+fn segments_intersect(p1: &Point, p2: &Point, p3: &Point, p4: &Point) -> bool {
+    let d1 = cross(p3, p4, p1).signum();
+    let d2 = cross(p3, p4, p2).signum();
+    let d3 = cross(p1, p2, p3).signum();
+    let d4 = cross(p1, p2, p4).signum();
+
+    if d1 * d2 < 0 && d3 * d4 < 0 {
+        return true;
+    }
+
+    (d1 == 0 && on_segment(p3, p1, p4))
+        || (d2 == 0 && on_segment(p3, p2, p4))
+        || (d3 == 0 && on_segment(p1, p3, p2))
+        || (d4 == 0 && on_segment(p1, p4, p2))
+}
+
+// Edges sharing a vertex only overlap if the second one doubles back over the first
+fn adjacent_edges_overlap(a: (&Point, &Point), b: (&Point, &Point)) -> bool {
+    let da = Vector::new(a.1.x - a.0.x, a.1.y - a.0.y);
+    let db = Vector::new(b.1.x - b.0.x, b.1.y - b.0.y);
+
+    da.x * db.y - da.y * db.x == 0 && da.x * db.x + da.y * db.y < 0
+}
+
+impl Polygon {
+    // Walk the vectors from the origin, keeping the origin as the first vertex
+    pub fn from_vectors(vectors: &[Vector]) -> Polygon {
+        let mut vertices = vec![Point { x: 0, y: 0 }];
+        for v in vectors {
+            let next = add(vertices.last().unwrap(), v);
+            vertices.push(next);
+        }
+        Polygon { vertices }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.vertices.first() == self.vertices.last()
+    }
+
+    fn edges(&self) -> Vec<(&Point, &Point)> {
+        self.vertices.windows(2).map(|w| (&w[0], &w[1])).collect()
+    }
+
+    // Shoelace sum, positive for counter clockwise winding (y pointing up)
+    pub fn twice_signed_area(&self) -> i64 {
+        self.vertices
+            .windows(2)
+            .map(|w| w[0].x * w[1].y - w[1].x * w[0].y)
+            .sum()
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        match self.twice_signed_area().signum() {
+            1 => Orientation::CounterClockwise,
+            -1 => Orientation::Clockwise,
+            _ => Orientation::Degenerate,
+        }
+    }
+
+    // Pairs of edge indices (i < j) that touch or cross outside of their shared vertex
+    pub fn self_intersections(&self) -> Vec<(usize, usize)> {
+        let edges = self.edges();
+        let n = edges.len();
+        let closed = self.is_closed();
+
+        let mut intersections = Vec::new();
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let adjacent = j == i + 1 || (closed && i == 0 && j == n - 1);
+                let hit = if adjacent {
+                    if j == i + 1 {
+                        adjacent_edges_overlap(edges[i], edges[j])
+                    } else {
+                        adjacent_edges_overlap(edges[j], edges[i])
+                    }
+                } else {
+                    segments_intersect(edges[i].0, edges[i].1, edges[j].0, edges[j].1)
+                };
+                if hit {
+                    intersections.push((i, j));
+                }
+            }
+        }
+        intersections
+    }
+
+    pub fn validate(&self) -> Result<Orientation, PolygonError> {
+        if !self.is_closed() {
+            return Err(PolygonError::NotClosed {
+                start: self.vertices.first().unwrap().clone(),
+                end: self.vertices.last().unwrap().clone(),
+            });
+        }
+
+        let intersections = self.self_intersections();
+        if !intersections.is_empty() {
+            return Err(PolygonError::SelfIntersecting(intersections));
+        }
+
+        Ok(self.orientation())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions_to_vector;
+
+    fn polygon(instructions: &[(&str, i64)]) -> Polygon {
+        let vectors = instructions.iter()
+            .map(|(direction, length)| instructions_to_vector(direction, *length))
+            .collect::<Vec<_>>();
+        Polygon::from_vectors(&vectors)
+    }
+
+    #[test]
+    fn open_plan_is_not_closed() {
+        assert_eq!(
+            polygon(&[("R", 2), ("U", 2)]).validate(),
+            Err(PolygonError::NotClosed { start: Point { x: 0, y: 0 }, end: Point { x: 2, y: 2 } })
+        );
+    }
+
+    #[test]
+    fn crossing_plan_reports_the_crossing_edges() {
+        // The fourth edge runs down through the middle of the first one
+        let plan = polygon(&[("R", 2), ("U", 2), ("L", 1), ("D", 3), ("L", 1), ("U", 1)]);
+        assert_eq!(plan.validate(), Err(PolygonError::SelfIntersecting(vec![(0, 3)])));
+    }
+
+    #[test]
+    fn doubling_back_overlaps_the_previous_edge() {
+        assert_eq!(polygon(&[("R", 2), ("L", 2)]).self_intersections(), vec![(0, 1)]);
+        // Turning corners and the closing corner are fine
+        assert!(polygon(&[("R", 2), ("U", 1), ("L", 2), ("D", 1)]).self_intersections().is_empty());
+    }
+
+    #[test]
+    fn orientation_follows_the_winding() {
+        let counter_clockwise = polygon(&[("R", 2), ("U", 1), ("L", 2), ("D", 1)]);
+        let clockwise = polygon(&[("U", 1), ("R", 2), ("D", 1), ("L", 2)]);
+
+        assert_eq!(counter_clockwise.validate(), Ok(Orientation::CounterClockwise));
+        assert_eq!(clockwise.validate(), Ok(Orientation::Clockwise));
+        assert_eq!(polygon(&[("R", 2), ("L", 2)]).orientation(), Orientation::Degenerate);
+    }
+}
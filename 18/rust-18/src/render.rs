@@ -0,0 +1,215 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use aoc_common::{Grid, ParseError, Span};
+
+use crate::polygon::Polygon;
+use crate::{polygon_limits, Point};
+
+/** Structs */
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const fn new(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b }
+    }
+}
+
+pub struct Image {
+    pixels: Grid<Color>,
+}
+
+/** Statics */
+
+const BACKGROUND: Color = Color::new(255, 255, 255);
+const INTERIOR: Color = Color::new(190, 190, 190);
+
+/** Functions */
+
+// Parse the "(#70c710)" color word of an instruction
+pub fn parse_color(word: &Span) -> Result<Color, ParseError> {
+    let hex = word.text.strip_prefix("(#").and_then(|w| w.strip_suffix(')'))
+        .filter(|hex| hex.len() == 6 && hex.chars().all(|d| d.is_ascii_hexdigit()))
+        .ok_or_else(|| word.error(format!("expected a '(#rrggbb)' color, found '{}'", word.text)))?;
+    let value = u32::from_str_radix(hex, 16).map_err(|_| word.error("color is not hexadecimal"))?;
+
+    Ok(Color::new((value >> 16) as u8, (value >> 8) as u8, value as u8))
+}
+
+impl Image {
+    fn new(width: usize, height: usize, color: Color) -> Image {
+        Image {
+            pixels: Grid::new(width, height, color),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.pixels.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.pixels.height()
+    }
+
+    // Pixels outside the image are ignored
+    fn set(&mut self, x: usize, y: usize, color: Color) {
+        if let Some(pixel) = self.pixels.get_mut(x, y) {
+            *pixel = color;
+        }
+    }
+
+    fn raw_rgb(&self) -> Vec<u8> {
+        self.pixels.iter().flat_map(|c| [c.r, c.g, c.b]).collect()
+    }
+
+    pub fn write_ppm(&self, path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        write!(out, "P6\n{} {}\n255\n", self.width(), self.height())?;
+        out.write_all(&self.raw_rgb())?;
+        out.flush()
+    }
+
+    pub fn write_png(&self, path: &Path) -> io::Result<()> {
+        let out = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(out, self.width() as u32, self.height() as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.raw_rgb())?;
+        Ok(())
+    }
+
+    // Pick the format from the file extension, defaulting to PPM
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => self.write_png(path),
+            _ => self.write_ppm(path),
+        }
+    }
+}
+
+// x coordinates where the edges cross the horizontal line through row y.
+// Edges are treated as half open in y so shared vertices are only counted once.
+fn row_crossings(polygon: &Polygon, y: f64) -> Vec<f64> {
+    let mut crossings = polygon
+        .vertices
+        .windows(2)
+        .filter(|w| (w[0].y as f64 <= y) != (w[1].y as f64 <= y))
+        .map(|w| {
+            let (a, b) = (&w[0], &w[1]);
+            a.x as f64 + (y - a.y as f64) * (b.x - a.x) as f64 / (b.y - a.y) as f64
+        })
+        .collect::<Vec<_>>();
+    crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    crossings
+}
+
+// Draw the lagoon with every trench segment in its own color. Plans larger than
+// max_size in either direction are scaled down so that each pixel covers a block of cells.
+pub fn render_lagoon(polygon: &Polygon, colors: &[Color], max_size: usize) -> Image {
+    let (min, max) = polygon_limits(&polygon.vertices);
+    let extent = (max.x - min.x + 1).max(max.y - min.y + 1);
+    let scale = (extent as usize).div_ceil(max_size.max(1)).max(1) as i64;
+
+    let width = ((max.x - min.x) / scale + 1) as usize;
+    let height = ((max.y - min.y) / scale + 1) as usize;
+    let mut image = Image::new(width, height, BACKGROUND);
+
+    // Image rows go top down, y goes bottom up
+    let to_pixel = |p: &Point| -> (i64, i64) {
+        ((p.x - min.x) / scale, (height as i64 - 1) - (p.y - min.y) / scale)
+    };
+
+    // Interior, sampled at the lower left cell of each pixel block
+    for row in 0..height {
+        let y = (min.y + (height - 1 - row) as i64 * scale) as f64;
+        let crossings = row_crossings(polygon, y);
+        for col in 0..width {
+            let x = (min.x + col as i64 * scale) as f64;
+            let left = crossings.iter().filter(|c| **c < x).count();
+            if left % 2 == 1 {
+                image.set(col, row, INTERIOR);
+            }
+        }
+    }
+
+    // Trench, one segment at a time
+    for (edge, color) in polygon.vertices.windows(2).zip(colors) {
+        let (x0, y0) = to_pixel(&edge[0]);
+        let (x1, y1) = to_pixel(&edge[1]);
+        let steps = (x1 - x0).abs().max((y1 - y0).abs());
+        for i in 0..=steps {
+            let (x, y) = if steps == 0 {
+                (x0, y0)
+            } else {
+                (x0 + (x1 - x0) * i / steps, y0 + (y1 - y0) * i / steps)
+            };
+            image.set(x as usize, y as usize, *color);
+        }
+    }
+
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions_to_vector;
+
+    const RED: Color = Color::new(255, 0, 0);
+    const GREEN: Color = Color::new(0, 255, 0);
+    const BLUE: Color = Color::new(0, 0, 255);
+    const BLACK: Color = Color::new(0, 0, 0);
+
+    fn polygon(instructions: &[(&str, i64)]) -> Polygon {
+        let vectors = instructions.iter()
+            .map(|(direction, length)| instructions_to_vector(direction, *length))
+            .collect::<Vec<_>>();
+        Polygon::from_vectors(&vectors)
+    }
+
+    #[test]
+    fn colors_are_read_from_the_color_word() {
+        let word = |text| Span { line: 3, column: 7, text };
+
+        assert_eq!(parse_color(&word("(#70c710)")), Ok(Color::new(0x70, 0xc7, 0x10)));
+        for text in ["(#70c7)", "(#70c7zz)", "(#+0c710)", "70c710"] {
+            let message = format!("expected a '(#rrggbb)' color, found '{}'", text);
+            assert_eq!(parse_color(&word(text)), Err(ParseError::new(3, 7, message)));
+        }
+    }
+
+    #[test]
+    fn square_is_drawn_edge_by_edge_around_its_interior() {
+        let square = polygon(&[("R", 2), ("U", 2), ("L", 2), ("D", 2)]);
+        let image = render_lagoon(&square, &[RED, GREEN, BLUE, BLACK], 100);
+        let pixel = |x, y| *image.pixels.get(x, y).unwrap();
+
+        assert_eq!((image.width(), image.height()), (3, 3));
+        // Image rows go top down, so the first edge is the bottom row
+        assert_eq!(pixel(1, 2), RED);
+        assert_eq!(pixel(2, 1), GREEN);
+        assert_eq!(pixel(1, 0), BLUE);
+        assert_eq!(pixel(0, 1), BLACK);
+        // Later edges are drawn over the corners they share with earlier ones
+        assert_eq!(pixel(0, 2), BLACK);
+        assert_eq!(pixel(1, 1), INTERIOR);
+    }
+
+    #[test]
+    fn large_plans_are_scaled_to_fit() {
+        let rectangle = polygon(&[("R", 9), ("U", 3), ("L", 9), ("D", 3)]);
+        let image = render_lagoon(&rectangle, &[RED; 4], 5);
+
+        assert_eq!((image.width(), image.height()), (5, 2));
+        assert!(image.pixels.iter().all(|c| *c == RED));
+    }
+}
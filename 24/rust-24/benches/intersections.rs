@@ -0,0 +1,31 @@
+use aoc_common::Rng;
+use clap::ValueEnum;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rust_24::chords::PairCounter;
+use rust_24::generate::hailstorm;
+use rust_24::intersection::TestArea;
+use rust_24::parse::hailstones_from_str;
+
+/** Functions */
+
+fn pair_loop(c: &mut Criterion) {
+    let mut rng = Rng::new(24);
+    let inputs = [
+        ("example", hailstones_from_str(include_str!("../example.txt"), "example.txt").unwrap(), TestArea::square(7, 27)),
+        ("input", hailstones_from_str(include_str!("../input.txt"), "input.txt").unwrap(), TestArea::PUZZLE),
+        ("synthetic_1000", hailstorm(&mut rng, 1000).hailstones, TestArea::PUZZLE),
+    ];
+
+    let mut group = c.benchmark_group("day24/count_intersections");
+    group.sample_size(10);
+    for (name, hailstones, area) in &inputs {
+        for counter in PairCounter::value_variants() {
+            let id = BenchmarkId::new(format!("{:?}", counter), name);
+            group.bench_with_input(id, hailstones, |b, hailstones| b.iter(|| counter.count(hailstones, area)));
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, pair_loop);
+criterion_main!(benches);
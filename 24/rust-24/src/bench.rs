@@ -0,0 +1,63 @@
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+
+use aoc_common::AocError;
+
+use crate::chords::PairCounter;
+use crate::intersection::TestArea;
+use crate::Hailstone;
+
+/** Functions */
+
+// Best of `runs` wall clock times for every part 1 counter, checking they all agree
+pub fn bench_counters(hailstones: &[Hailstone], area: &TestArea, runs: usize) -> Result<(), AocError> {
+    let mut expected: Option<(PairCounter, usize)> = None;
+
+    for counter in PairCounter::value_variants() {
+        let mut best = Duration::MAX;
+        let mut count = 0;
+        for _ in 0..runs.max(1) {
+            let start = Instant::now();
+            count = counter.count(hailstones, area);
+            best = best.min(start.elapsed());
+        }
+
+        println!("{:<10} {:>8} intersections  best of {}: {:?}", format!("{:?}", counter), count, runs.max(1), best);
+        match expected {
+            None => expected = Some((*counter, count)),
+            Some((first, e)) if e != count => {
+                return Err(AocError::Mismatch(format!(
+                    "{:?} counter found {} intersections, {:?} found {}",
+                    counter, count, first, e
+                )));
+            },
+            Some(_) => (),
+        }
+    }
+    println!("Threads: {}", rayon::current_num_threads());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::parse_hailstones;
+    use std::path::Path;
+
+    #[test]
+    fn counters_agree_on_example() {
+        let hailstones = parse_hailstones(Path::new("example.txt")).unwrap();
+        let area = TestArea::square(7, 27);
+        assert!(bench_counters(&hailstones, &area, 1).is_ok());
+    }
+
+    #[test]
+    fn parallel_matches_sequential_on_input() {
+        let hailstones = parse_hailstones(Path::new("input.txt")).unwrap();
+        assert_eq!(
+            PairCounter::Parallel.count(&hailstones, &TestArea::PUZZLE),
+            PairCounter::Brute.count(&hailstones, &TestArea::PUZZLE)
+        );
+    }
+}
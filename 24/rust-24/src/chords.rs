@@ -0,0 +1,414 @@
+use std::cmp::Ordering;
+
+use clap::ValueEnum;
+
+use crate::intersection::{exact_crossing, ExactCrossing, TestArea};
+use crate::{count_intersections, count_intersections_parallel, Hailstone};
+
+// Counting crossings without testing pairs. Clipped to a rectangle, a forward ray that starts
+// outside it is a chord between two points of the boundary, and two chords cross exactly when
+// their endpoints alternate around the boundary. Sorting the endpoints along the boundary and
+// counting the alternating pairs with a Fenwick tree takes O(n log n) for n chords.
+//
+// Rays starting inside the rectangle do not fit that picture, so the area is cut at the median
+// of those starts, alternating between x and y, until each part holds only a few of them. A ray
+// starting on a cut starts on the boundary of both parts. The few rays left starting inside a
+// part, and the ones that only touch it in a single point or run along its boundary, are tested
+// against every other ray of the part with the exact predicate. A part owns its lower bounds
+// and only the upper bounds of the whole area, so a crossing on a cut is counted once.
+//
+// A line crosses O(sqrt(k)) parts of a tree cut around k starts, which makes the count
+// O(n sqrt(k) log n) instead of the n^2 / 2 pair tests of the other counters.
+
+/** Structs */
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum PairCounter {
+    /// Test every pair of hailstones
+    Brute,
+    /// Test every pair, spread over all cores
+    Parallel,
+    /// Count crossings of the clipped paths from their order around the area boundary
+    Chords,
+}
+
+impl PairCounter {
+    pub fn count(&self, hailstones: &[Hailstone], area: &TestArea) -> usize {
+        match self {
+            PairCounter::Brute => count_intersections(hailstones, area),
+            PairCounter::Parallel => count_intersections_parallel(hailstones, area),
+            PairCounter::Chords => count_intersections_chords(hailstones, area),
+        }
+    }
+}
+
+// Time num / den along a ray, with a positive denominator
+#[derive(Debug, Clone, Copy)]
+struct Time {
+    num: i128,
+    den: i128,
+}
+
+// Point on the boundary of a part. Sides are numbered counterclockwise from the bottom one,
+// each holding its first corner, and `along` grows counterclockwise as a multiple of 1 / den.
+#[derive(Debug, Clone, Copy)]
+struct BoundaryPoint {
+    side: u8,
+    along: i128,
+    den: i128,
+    owned: bool,
+}
+
+// Rectangle of the area with the crossings it counts
+#[derive(Debug, Clone, Copy)]
+struct Part {
+    min_x: i128,
+    max_x: i128,
+    min_y: i128,
+    max_y: i128,
+    // Whether crossings on the upper bounds belong to this part, rather than to its neighbour
+    owns_max_x: bool,
+    owns_max_y: bool,
+}
+
+// What is left of a forward ray inside a part
+enum Piece {
+    Miss,
+    Chord(BoundaryPoint, BoundaryPoint),
+    // Starts inside, touches the part in a single point or runs along its boundary
+    Single,
+}
+
+// Counts of inserted values, for prefix sums in O(log n)
+struct Fenwick {
+    counts: Vec<usize>,
+}
+
+/** Statics */
+
+// Starts inside a part below which its rays are no longer split up
+const LEAF_STARTS: usize = 32;
+
+/** Functions */
+
+impl Time {
+    fn new(num: i128, den: i128) -> Time {
+        if den < 0 { Time { num: -num, den: -den } } else { Time { num, den } }
+    }
+
+    fn cmp(&self, other: &Time) -> Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+impl BoundaryPoint {
+    // Position around the boundary, ownership plays no part
+    fn cmp(&self, other: &BoundaryPoint) -> Ordering {
+        self.side.cmp(&other.side).then((self.along * other.den).cmp(&(other.along * self.den)))
+    }
+}
+
+impl Part {
+    fn whole(area: &TestArea) -> Part {
+        Part {
+            min_x: area.min_x,
+            max_x: area.max_x,
+            min_y: area.min_y,
+            max_y: area.max_y,
+            owns_max_x: true,
+            owns_max_y: true,
+        }
+    }
+
+    fn starts_inside(&self, h: &Hailstone) -> bool {
+        let p = h.position_exact();
+        self.min_x < p.x && p.x < self.max_x && self.min_y < p.y && p.y < self.max_y
+    }
+
+    fn runs_along_boundary(&self, h: &Hailstone) -> bool {
+        let (p, v) = (h.position_exact(), h.velocity_exact());
+        (v.x == 0 && (p.x == self.min_x || p.x == self.max_x)) || (v.y == 0 && (p.y == self.min_y || p.y == self.max_y))
+    }
+
+    fn owns_point(&self, x_num: i128, y_num: i128, den: i128) -> bool {
+        let within = |num: i128, min: i128, max: i128, owns_max: bool| {
+            num >= min * den && (num < max * den || (owns_max && num == max * den))
+        };
+        within(x_num, self.min_x, self.max_x, self.owns_max_x) && within(y_num, self.min_y, self.max_y, self.owns_max_y)
+    }
+
+    fn owns(&self, c: &ExactCrossing) -> bool {
+        self.owns_point(c.x_num, c.y_num, c.den)
+    }
+
+    // Point x_num / den, y_num / den on the boundary
+    fn boundary_point(&self, x_num: i128, y_num: i128, den: i128) -> BoundaryPoint {
+        let (side, along) = if y_num == self.min_y * den && x_num < self.max_x * den {
+            (0, x_num)
+        } else if x_num == self.max_x * den && y_num < self.max_y * den {
+            (1, y_num)
+        } else if y_num == self.max_y * den && x_num > self.min_x * den {
+            (2, -x_num)
+        } else {
+            (3, -y_num)
+        };
+        BoundaryPoint { side, along, den, owned: self.owns_point(x_num, y_num, den) }
+    }
+
+    // Liang-Barsky clipping of p + v*t for t >= 0, exact in rational times
+    fn clip(&self, h: &Hailstone) -> Piece {
+        let (p, v) = (h.position_exact(), h.velocity_exact());
+        let axes = [(p.x, v.x, self.min_x, self.max_x), (p.y, v.y, self.min_y, self.max_y)];
+
+        let mut t_in = Time { num: 0, den: 1 };
+        let mut t_out: Option<Time> = None;
+        for (p, v, lo, hi) in axes {
+            if v == 0 {
+                if p < lo || p > hi {
+                    return Piece::Miss;
+                }
+                continue;
+            }
+            let (near, far) = if v > 0 { (lo, hi) } else { (hi, lo) };
+            let (near, far) = (Time::new(near - p, v), Time::new(far - p, v));
+            if near.cmp(&t_in) == Ordering::Greater {
+                t_in = near;
+            }
+            if t_out.is_none_or(|t| far.cmp(&t) == Ordering::Less) {
+                t_out = Some(far);
+            }
+        }
+
+        // A hailstone standing still in XY is parallel to everything
+        let Some(t_out) = t_out else {
+            return Piece::Miss;
+        };
+        match t_in.cmp(&t_out) {
+            Ordering::Greater => Piece::Miss,
+            Ordering::Equal => Piece::Single,
+            Ordering::Less if self.starts_inside(h) || self.runs_along_boundary(h) => Piece::Single,
+            Ordering::Less => {
+                let at = |t: Time| self.boundary_point(p.x * t.den + v.x * t.num, p.y * t.den + v.y * t.num, t.den);
+                Piece::Chord(at(t_in), at(t_out))
+            }
+        }
+    }
+
+    // The part below and the part above `cut` on the x axis, or on the y axis
+    fn split(&self, cut: i128, on_x: bool) -> (Part, Part) {
+        let (mut low, mut high) = (*self, *self);
+        if on_x {
+            (low.max_x, low.owns_max_x, high.min_x) = (cut, false, cut);
+        } else {
+            (low.max_y, low.owns_max_y, high.min_y) = (cut, false, cut);
+        }
+        (low, high)
+    }
+}
+
+impl Fenwick {
+    fn new(size: usize) -> Fenwick {
+        Fenwick { counts: vec![0; size + 1] }
+    }
+
+    fn insert(&mut self, index: usize) {
+        let mut i = index + 1;
+        while i < self.counts.len() {
+            self.counts[i] += 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    // Number of inserted values below `end`
+    fn count_below(&self, end: usize) -> usize {
+        let mut i = end;
+        let mut count = 0;
+        while i > 0 {
+            count += self.counts[i];
+            i -= i & i.wrapping_neg();
+        }
+        count
+    }
+}
+
+fn pairs(n: usize) -> usize {
+    n * n.saturating_sub(1) / 2
+}
+
+// Pairs of chords that cross, inside the part or at an endpoint they share on an owned part
+// of the boundary. Chords sharing both endpoints lie on the same line and never cross.
+fn count_chord_crossings(chords: &[(BoundaryPoint, BoundaryPoint)]) -> usize {
+    let mut ends = chords.iter().enumerate().flat_map(|(i, (a, b))| [(a, i), (b, i)]).collect::<Vec<_>>();
+    ends.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    // Equal points get equal ranks, and chords meeting at an owned point cross there
+    let mut ranks = vec![Vec::with_capacity(2); chords.len()];
+    let mut owned = Vec::new();
+    let mut meeting = 0;
+    let mut run = 0;
+    for (k, (point, chord)) in ends.iter().enumerate() {
+        if k > 0 && point.cmp(ends[k - 1].0) != Ordering::Equal {
+            meeting += if owned[owned.len() - 1] { pairs(run) } else { 0 };
+            run = 0;
+        }
+        if run == 0 {
+            owned.push(point.owned);
+        }
+        run += 1;
+        ranks[*chord].push(owned.len() - 1);
+    }
+    if owned.last() == Some(&true) {
+        meeting += pairs(run);
+    }
+
+    let mut spans = ranks.iter().map(|r| (r[0].min(r[1]), r[0].max(r[1]))).collect::<Vec<_>>();
+    spans.sort();
+
+    // Both ends shared were counted once for every owned end, though the chords do not cross
+    let mut same = 0;
+    for group in spans.chunk_by(|a, b| a == b) {
+        let (lo, hi) = group[0];
+        same += pairs(group.len()) * (usize::from(owned[lo]) + usize::from(owned[hi]));
+    }
+
+    // Chords starting earlier that end strictly inside this one cross it
+    let mut ends_seen = Fenwick::new(owned.len());
+    let mut alternating = 0;
+    for group in spans.chunk_by(|a, b| a.0 == b.0) {
+        for (lo, hi) in group {
+            alternating += ends_seen.count_below(*hi) - ends_seen.count_below(lo + 1);
+        }
+        for (_, hi) in group {
+            ends_seen.insert(*hi);
+        }
+    }
+
+    alternating + meeting - same
+}
+
+// Crossings owned by the part between the rays in `rays`, as indices into `hailstones`
+fn count_part(hailstones: &[Hailstone], rays: &[usize], part: &Part, depth: usize) -> usize {
+    let mut present = Vec::new();
+    let mut chords = Vec::new();
+    let mut singles = Vec::new();
+    let mut starts = Vec::new();
+    for &i in rays {
+        let h = &hailstones[i];
+        match part.clip(h) {
+            Piece::Miss => continue,
+            Piece::Chord(a, b) => chords.push((a, b)),
+            Piece::Single => singles.push(present.len()),
+        }
+        if part.starts_inside(h) {
+            starts.push(i);
+        }
+        present.push(i);
+    }
+
+    if starts.len() > LEAF_STARTS {
+        let on_x = depth.is_multiple_of(2);
+        let mut coordinates = starts
+            .iter()
+            .map(|i| if on_x { hailstones[*i].p.x } else { hailstones[*i].p.y })
+            .collect::<Vec<_>>();
+        let (_, median, _) = coordinates.select_nth_unstable(starts.len() / 2);
+        let (low, high) = part.split(i128::from(*median), on_x);
+        return count_part(hailstones, &present, &low, depth + 1) + count_part(hailstones, &present, &high, depth + 1);
+    }
+
+    // Every single against every other ray, pairs of singles from the first one only
+    let mut is_single = vec![false; present.len()];
+    for k in &singles {
+        is_single[*k] = true;
+    }
+    let mut count = count_chord_crossings(&chords);
+    for k in singles {
+        let h = &hailstones[present[k]];
+        count += (0..present.len())
+            .filter(|j| *j != k && !(is_single[*j] && *j < k))
+            .filter(|j| exact_crossing(h, &hailstones[present[*j]]).is_ok_and(|c| part.owns(&c)))
+            .count();
+    }
+    count
+}
+
+pub fn count_intersections_chords(hailstones: &[Hailstone], area: &TestArea) -> usize {
+    let rays = (0..hailstones.len()).collect::<Vec<_>>();
+    count_part(hailstones, &rays, &Part::whole(area), 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::hailstorm;
+    use crate::parse::parse_hailstones;
+    use crate::{Point, Vector};
+    use aoc_common::Rng;
+    use std::path::Path;
+
+    fn random_hailstones(rng: &mut Rng, n: usize, coordinates: i64, speed: i64) -> Vec<Hailstone> {
+        (0..n)
+            .map(|_| Hailstone {
+                p: Point { x: rng.range(0, coordinates), y: rng.range(0, coordinates), z: 0 },
+                v: Vector::new(rng.range(-speed, speed + 1), rng.range(-speed, speed + 1), 0),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn chords_match_brute_force_on_example_and_input() {
+        let cases = [("example.txt", TestArea::square(7, 27)), ("input.txt", TestArea::PUZZLE)];
+        for (filename, area) in cases {
+            let hailstones = parse_hailstones(Path::new(filename)).unwrap();
+            assert_eq!(count_intersections_chords(&hailstones, &area), count_intersections(&hailstones, &area));
+        }
+    }
+
+    // Small integer coordinates put plenty of starts, crossings and shared chord ends exactly on
+    // the area bounds and the cuts, with many hailstones running along them or on the same line
+    #[test]
+    fn chords_match_brute_force_on_random_hailstones() {
+        let mut rng = Rng::new(24);
+        let areas = [
+            TestArea::square(20, 80),
+            TestArea { min_x: 0, max_x: 100, min_y: 40, max_y: 60 },
+            TestArea { min_x: 50, max_x: 50, min_y: 0, max_y: 100 },
+            TestArea::square(3, 3),
+        ];
+
+        for (n, coordinates, speed) in [(2, 100, 5), (10, 100, 5), (50, 100, 5), (200, 100, 5), (300, 10, 2), (300, 1000, 50)] {
+            let hailstones = random_hailstones(&mut rng, n, coordinates, speed);
+            for area in &areas {
+                assert_eq!(
+                    count_intersections_chords(&hailstones, area),
+                    count_intersections(&hailstones, area),
+                    "{} hailstones in {:?}",
+                    n,
+                    area
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn chords_match_a_generated_hailstorm() {
+        let storm = hailstorm(&mut Rng::new(24), 2000);
+        assert_eq!(count_intersections_chords(&storm.hailstones, &TestArea::PUZZLE), storm.part1());
+    }
+
+    #[test]
+    fn chords_cross_when_their_ends_alternate() {
+        let part = Part::whole(&TestArea::square(0, 10));
+        let at = |x, y| part.boundary_point(x, y, 1);
+        let chord = |a: (i128, i128), b: (i128, i128)| (at(a.0, a.1), at(b.0, b.1));
+
+        // The diagonals cross inside, a chord across the corner at (10, 0) crosses neither
+        let diagonals = [chord((0, 0), (10, 10)), chord((0, 10), (10, 0))];
+        assert_eq!(count_chord_crossings(&diagonals), 1);
+        assert_eq!(count_chord_crossings(&[diagonals[0], chord((5, 0), (10, 5))]), 0);
+
+        // Sharing one end is a crossing on the boundary, sharing both is the same line
+        assert_eq!(count_chord_crossings(&[diagonals[0], chord((0, 0), (10, 5))]), 1);
+        assert_eq!(count_chord_crossings(&[diagonals[0], chord((10, 10), (0, 0))]), 0);
+    }
+}
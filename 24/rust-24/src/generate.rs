@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+
+use aoc_common::Rng;
+
+use crate::intersection::TestArea;
+use crate::simulate::Rock;
+use crate::{Hailstone, Point, Vector};
+
+// Random hailstorms with known answers. A rock is thrown first and every hailstone is then
+// placed so the rock hits it at its own whole time t:
+//   p_i = P + (V - v_i) * t_i
+// which makes the part 2 answer the rock's coordinates by construction.
+//
+// For part 1 the XY velocities come in two families, laid out in a skewed frame where one family
+// moves along x and the other along y. Hailstones of the same family are parallel. A hailstone
+// of each family meet where the horizontal line of one crosses the vertical line of the other,
+// and whether both reach that point and it lies in the test area comes down to comparing
+// integers, without the line intersections the counters solve. The frame is an integer shear
+// with determinant 1, so positions and velocities stay whole in both frames and times are equal.
+//
+// Magnitudes follow the real input: positions around the 2e14..4e14 test area, velocities of a
+// few hundred and hit times up to 1e12, which keeps p_i well inside i64.
+
+/** Structs */
+
+pub struct Hailstorm {
+    pub hailstones: Vec<Hailstone>,
+    pub rock: Rock,
+    // Pairs crossing in TestArea::PUZZLE, the part 1 answer
+    pub crossings: usize,
+}
+
+// Path of a hailstone in the skewed frame, along x on the line y = line starting from x = start,
+// or the other way round for the other family
+struct Track {
+    line: i64,
+    start: i64,
+    speed: i64,
+}
+
+// (x, y) -> (x + k*y, m*x + (m*k + 1)*y) from the skewed frame to the real one
+struct Shear {
+    k: i64,
+    m: i64,
+}
+
+/** Statics */
+
+// Largest speed of a family in the skewed frame, the shear makes real speeds up to 7 times that
+const MAX_SPEED: i64 = 100;
+const MAX_SPEED_Z: i64 = 300;
+const MAX_TIME: i64 = 1_000_000_000_000;
+const SHEAR_FACTORS: [i64; 4] = [-2, -1, 1, 2];
+
+/** Functions */
+
+impl Shear {
+    fn to_real(&self, x: i64, y: i64) -> (i64, i64) {
+        (x + self.k * y, self.m * x + (self.m * self.k + 1) * y)
+    }
+
+    fn to_skewed(&self, x: i64, y: i64) -> (i64, i64) {
+        ((self.m * self.k + 1) * x - self.k * y, y - self.m * x)
+    }
+}
+
+impl Hailstorm {
+    // One "px, py, pz @ vx, vy, vz" line per hailstone
+    pub fn text(&self) -> String {
+        let lines = self
+            .hailstones
+            .iter()
+            .map(|h| format!("{}, {}, {} @ {}, {}, {}", h.p.x, h.p.y, h.p.z, h.v.x, h.v.y, h.v.z))
+            .collect::<Vec<_>>();
+        lines.join("\n")
+    }
+
+    pub fn part1(&self) -> usize {
+        self.crossings
+    }
+
+    pub fn part2(&self) -> i128 {
+        self.rock.p.x + self.rock.p.y + self.rock.p.z
+    }
+}
+
+// Track along x and track along y meet at (vertical.line, horizontal.line). The pair counts when
+// both get there at t >= 0 and the point, back in the real frame, lies in the area.
+fn count_crossings(horizontals: &[Track], verticals: &[Track], shear: &Shear, area: &TestArea) -> usize {
+    let ahead = |track: &Track, to: i64| (to - track.start) * track.speed.signum() >= 0;
+    let inside = |x: i64, y: i64| {
+        let (x, y) = (i128::from(x), i128::from(y));
+        (area.min_x..=area.max_x).contains(&x) && (area.min_y..=area.max_y).contains(&y)
+    };
+
+    let mut count = 0;
+    for h in horizontals {
+        for v in verticals {
+            let (x, y) = shear.to_real(v.line, h.line);
+            if ahead(h, v.line) && ahead(v, h.line) && inside(x, y) {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+pub fn hailstorm(rng: &mut Rng, count: usize) -> Hailstorm {
+    let factor = |rng: &mut Rng| SHEAR_FACTORS[rng.below(SHEAR_FACTORS.len() as u64) as usize];
+    let shear = Shear { k: factor(rng), m: factor(rng) };
+    let coordinate = |rng: &mut Rng| rng.range(200_000_000_000_000, 400_000_000_000_000);
+
+    // The rock in both frames
+    let rock_p = Point::new(coordinate(rng), coordinate(rng), coordinate(rng));
+    let skewed_p = shear.to_skewed(rock_p.x, rock_p.y);
+    let skewed_v = (rng.range(-MAX_SPEED, MAX_SPEED + 1), rng.range(-MAX_SPEED, MAX_SPEED + 1));
+    let (vx, vy) = shear.to_real(skewed_v.0, skewed_v.1);
+    let rock_v = Vector::new(vx, vy, rng.range(-MAX_SPEED_Z, MAX_SPEED_Z + 1));
+
+    // Distinct times, so no two hailstones are hit at the same place
+    let mut times = HashSet::new();
+    let mut hailstones = Vec::with_capacity(count);
+    let (mut horizontals, mut verticals) = (Vec::new(), Vec::new());
+    while hailstones.len() < count {
+        let t = rng.range(1, MAX_TIME);
+        let speed = rng.range(1, MAX_SPEED + 1) * if rng.chance(0.5) { 1 } else { -1 };
+        let along_x = rng.chance(0.5);
+        let skewed = if along_x { (speed, 0) } else { (0, speed) };
+        let (vx, vy) = shear.to_real(skewed.0, skewed.1);
+        let v = Vector::new(vx, vy, rng.range(-MAX_SPEED_Z, MAX_SPEED_Z + 1));
+        if v == rock_v || !times.insert(t) {
+            continue;
+        }
+        hailstones.push(Hailstone { p: rock_p + (rock_v - v) * t, v });
+
+        let x = skewed_p.0 + (skewed_v.0 - skewed.0) * t;
+        let y = skewed_p.1 + (skewed_v.1 - skewed.1) * t;
+        if along_x {
+            horizontals.push(Track { line: y, start: x, speed });
+        } else {
+            verticals.push(Track { line: x, start: y, speed });
+        }
+    }
+
+    let rock = Rock { p: rock_p.to_i128(), v: rock_v.to_i128() };
+    let crossings = count_crossings(&horizontals, &verticals, &shear, &TestArea::PUZZLE);
+    Hailstorm { hailstones, rock, crossings }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::hailstones_from_str;
+    use crate::{count_intersections, solve_rock};
+
+    #[test]
+    fn generated_hailstorm_has_the_rock_as_answer() {
+        let mut rng = Rng::new(24);
+        for count in [3, 10, 100] {
+            let storm = hailstorm(&mut rng, count);
+            let hailstones = hailstones_from_str(&storm.text(), "generated").unwrap();
+            assert_eq!(hailstones.len(), count);
+
+            let (p, v) = solve_rock(&hailstones).expect("Generated rock not found");
+            assert_eq!((p, v), (storm.rock.p, storm.rock.v));
+            assert_eq!(storm.part2(), p.x + p.y + p.z);
+        }
+    }
+
+    #[test]
+    fn generated_hailstorm_has_the_designed_crossings() {
+        let mut rng = Rng::new(24);
+        for count in [2, 10, 300] {
+            let storm = hailstorm(&mut rng, count);
+            assert_eq!(storm.part1(), count_intersections(&storm.hailstones, &TestArea::PUZZLE), "{} hailstones", count);
+        }
+    }
+
+    #[test]
+    fn shear_keeps_whole_coordinates_both_ways() {
+        for k in SHEAR_FACTORS {
+            for m in SHEAR_FACTORS {
+                let shear = Shear { k, m };
+                let (x, y) = shear.to_real(7, -3);
+                assert_eq!(shear.to_skewed(x, y), (7, -3));
+            }
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use std::str::FromStr;
+
+use aoc_common::AocError;
+
+use crate::Hailstone;
+
+extern crate nalgebra as na;
+use na::{SMatrix, Vector3};
+
+/** Structs */
+
+// Inclusive bounds of the area in which crossings are counted. The bounds are i64 values,
+// widened so the exact predicates can scale them by a denominator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestArea {
+    pub min_x: i128,
+    pub max_x: i128,
+    pub min_y: i128,
+    pub max_y: i128,
+}
+
+// Where the XY paths of two hailstones meet, if they do so in the future
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Intersection {
+    Parallel,
+    InPastA,
+    InPastB,
+    Crosses { x: f64, y: f64, t1: f64, t2: f64 },
+}
+
+impl TestArea {
+    // The area of the real puzzle input, the example uses 7..27 instead
+    pub const PUZZLE: TestArea = TestArea::square(200000000000000, 400000000000000);
+
+    pub const fn square(min: i128, max: i128) -> TestArea {
+        TestArea { min_x: min, max_x: max, min_y: min, max_y: max }
+    }
+}
+
+// "7..27" for both axes, or "0..10,-5..5" for x and y on their own
+impl FromStr for TestArea {
+    type Err = AocError;
+
+    fn from_str(text: &str) -> Result<TestArea, AocError> {
+        let invalid = || AocError::InvalidInput(format!("expected a test area like 7..27 or 0..10,-5..5, found '{}'", text));
+        let range = |r: &str| -> Result<(i128, i128), AocError> {
+            let (min, max) = r.split_once("..").ok_or_else(invalid)?;
+            let bound = |b: &str| b.trim().parse::<i64>().map(i128::from).map_err(|_| invalid());
+            let bounds = (bound(min)?, bound(max)?);
+            if bounds.0 > bounds.1 {
+                return Err(invalid());
+            }
+            Ok(bounds)
+        };
+
+        let ((min_x, max_x), (min_y, max_y)) = match text.split_once(',') {
+            Some((x, y)) => (range(x)?, range(y)?),
+            None => (range(text)?, range(text)?),
+        };
+        Ok(TestArea { min_x, max_x, min_y, max_y })
+    }
+}
+
+impl Intersection {
+    // Future crossing inside the test area, bounds included
+    pub fn is_within(&self, area: &TestArea) -> bool {
+        match self {
+            Intersection::Crosses { x, y, .. } => {
+                *x >= area.min_x as f64 && *x <= area.max_x as f64
+                    && *y >= area.min_y as f64 && *y <= area.max_y as f64
+            }
+            _ => false,
+        }
+    }
+}
+
+// Classify a crossing from its times, hailstone A takes precedence when both are in the past
+fn classify(x: f64, y: f64, t1: f64, t2: f64) -> Intersection {
+    if t1 < 0.0 {
+        Intersection::InPastA
+    } else if t2 < 0.0 {
+        Intersection::InPastB
+    } else {
+        Intersection::Crosses { x, y, t1, t2 }
+    }
+}
+
+/** Functions */
+
+// Solve system of 2 equations directly
+pub fn hailstones_intersection(h1: &Hailstone, h2: &Hailstone) -> Intersection {
+
+    let x1 = h1.p.x as f64;
+    let y1 = h1.p.y as f64;
+    
+    let x2 = (h1.p.x + h1.v.x) as f64;
+    let y2 = (h1.p.y + h1.v.y) as f64;
+
+    let x3 = h2.p.x as f64;
+    let y3 = h2.p.y as f64;
+
+    let x4 = (h2.p.x + h2.v.x) as f64;
+    let y4 = (h2.p.y + h2.v.y) as f64;
+
+
+    let x_den = (x1 - x2) * (y3 - y4) - (y1 - y2) * (x3 - x4);
+
+    if x_den == 0.0 {
+        return Intersection::Parallel;
+    }
+
+    let x_nom = (x1*y2 - y1*x2) * (x3 - x4) - (x1 - x2) * (x3*y4 - y3*x4);
+
+    let y_nom = (x1*y2 - y1*x2) * (y3 - y4) - (y1 - y2) * (x3*y4 - y3*x4);
+    let y_den = (x1 - x2) * (y3 - y4) - (y1 - y2) * (x3 - x4);
+
+    let x = x_nom / x_den;
+    let y = y_nom / y_den;
+
+    // Times back from the crossing point along whichever axis the stone moves in
+    let t1 = if h1.v.x != 0 { (x - x1) / h1.v.x as f64 } else { (y - y1) / h1.v.y as f64 };
+    let t2 = if h2.v.x != 0 { (x - x3) / h2.v.x as f64 } else { (y - y3) / h2.v.y as f64 };
+
+    classify(x, y, t1, t2)
+}
+
+
+
+type Matrix2x2f = SMatrix<f64, 2, 2>;
+
+// Solve system of 2 equations using LU decomposition
+pub fn hailstones_intersection_2(h1: &Hailstone, h2: &Hailstone) -> Intersection {
+
+    // parametric form 
+
+    // 1: p1.x + v1.x * t1 = p2.x + v2.x * t2
+    // 2: p1.y + v1.y * t1 = p2.y + v2.y * t2
+
+    // 1: t1*v1.x - t2*v2.x = p2.x - p1.x
+    // 2: t1*v1.y - t2*v2.y = p2.y - p1.y
+
+    let v1 = Vector3::from(h1.v.to_f64());
+    let v2 = Vector3::from(h2.v.to_f64());
+
+    let a = Matrix2x2f::from_columns(&[v1.xy(), -v2.xy()]);
+    let b = Vector3::from((h2.p - h1.p).to_f64()).xy();
+
+    let decomp = a.lu();
+    let t = decomp.solve(&b);
+
+    if let Some(t) = t {
+        let crossing = h1.p.to_f64() + h1.v.to_f64() * t[0];
+
+        classify(crossing.x, crossing.y, t[0], t[1])
+    } else  {
+        Intersection::Parallel
+    }
+}
+
+
+// Crossing point and times as numerators over a shared positive denominator
+pub struct ExactCrossing {
+    pub x_num: i128,
+    pub y_num: i128,
+    pub t1_num: i128,
+    pub t2_num: i128,
+    pub den: i128,
+}
+
+impl ExactCrossing {
+    // The crossing point is compared as x*det against the bounds times det, so no division
+    // is ever needed. The test area includes its bounds.
+    pub fn is_within(&self, area: &TestArea) -> bool {
+        let x_range = (area.min_x * self.den)..=(area.max_x * self.den);
+        let y_range = (area.min_y * self.den)..=(area.max_y * self.den);
+        x_range.contains(&self.x_num) && y_range.contains(&self.y_num)
+    }
+}
+
+// Solve the same system exactly in i128. With Cramer's rule
+//   t1 = (v2.x*dy - v2.y*dx) / det,  t2 = (v1.x*dy - v1.y*dx) / det,  det = v2.x*v1.y - v1.x*v2.y
+// so parallel and past crossings are decided without any rounding.
+pub fn exact_crossing(h1: &Hailstone, h2: &Hailstone) -> Result<ExactCrossing, Intersection> {
+
+    let (p1, v1) = (h1.position_exact(), h1.velocity_exact());
+    let v2 = h2.velocity_exact();
+    let d = h2.position_exact() - p1;
+
+    let mut det = v2.x * v1.y - v1.x * v2.y;
+    if det == 0 {
+        return Err(Intersection::Parallel);
+    }
+
+    let mut t1_num = v2.x * d.y - v2.y * d.x;
+    let mut t2_num = v1.x * d.y - v1.y * d.x;
+
+    // Keep the denominator positive so the signs of the numerators are the signs of t1 and t2
+    if det < 0 {
+        det = -det;
+        t1_num = -t1_num;
+        t2_num = -t2_num;
+    }
+
+    if t1_num < 0 {
+        return Err(Intersection::InPastA);
+    }
+    if t2_num < 0 {
+        return Err(Intersection::InPastB);
+    }
+
+    let point = p1 * det + v1 * t1_num;
+    Ok(ExactCrossing {
+        x_num: point.x,
+        y_num: point.y,
+        t1_num,
+        t2_num,
+        den: det,
+    })
+}
+
+pub fn hailstones_intersection_exact(h1: &Hailstone, h2: &Hailstone) -> Intersection {
+    match exact_crossing(h1, h2) {
+        Ok(c) => {
+            let den = c.den as f64;
+            Intersection::Crosses {
+                x: c.x_num as f64 / den,
+                y: c.y_num as f64 / den,
+                t1: c.t1_num as f64 / den,
+                t2: c.t2_num as f64 / den,
+            }
+        }
+        Err(intersection) => intersection,
+    }
+}
+
+pub fn hailstones_intersects_within_exact(h1: &Hailstone, h2: &Hailstone, area: &TestArea) -> bool {
+    match exact_crossing(h1, h2) {
+        Ok(c) => c.is_within(area),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Point, Vector};
+
+    // Hailstone in the z = 0 plane
+    fn hailstone(px: i64, py: i64, vx: i64, vy: i64) -> Hailstone {
+        Hailstone { p: Point::new(px, py, 0), v: Vector::new(vx, vy, 0) }
+    }
+
+    #[test]
+    fn test_area_from_text() {
+        assert_eq!("7..27".parse::<TestArea>().unwrap(), TestArea::square(7, 27));
+        assert_eq!(
+            "0..10, -5..5".parse::<TestArea>().unwrap(),
+            TestArea { min_x: 0, max_x: 10, min_y: -5, max_y: 5 }
+        );
+        for text in ["7", "7..", "27..7", "a..b", "0..1,2", "0..9223372036854775808"] {
+            assert!(text.parse::<TestArea>().is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn parallel_and_coincident_paths_never_cross() {
+        let h = hailstone(0, 0, 1, 1);
+        let parallel = hailstone(0, 1, 2, 2);
+        let coincident = hailstone(1, 1, 1, 1);
+
+        for other in [parallel, coincident] {
+            assert_eq!(exact_crossing(&h, &other).err(), Some(Intersection::Parallel));
+            assert_eq!(hailstones_intersection_exact(&h, &other), Intersection::Parallel);
+        }
+    }
+
+    #[test]
+    fn crossings_in_the_past() {
+        let h = hailstone(0, 0, 1, 0);
+
+        // A would have been at (-5, 0) five time units ago
+        assert_eq!(exact_crossing(&h, &hailstone(-5, 5, 0, -1)).err(), Some(Intersection::InPastA));
+        // B was at (5, 0) five time units ago
+        assert_eq!(exact_crossing(&h, &hailstone(5, -5, 0, -1)).err(), Some(Intersection::InPastB));
+        // Both in the past reports A
+        assert_eq!(exact_crossing(&h, &hailstone(-5, -5, 0, -1)).err(), Some(Intersection::InPastA));
+    }
+
+    #[test]
+    fn crossing_on_the_area_boundary_counts() {
+        let (h1, h2) = (hailstone(0, 0, 1, 0), hailstone(7, 5, 0, -1));
+        let Ok(crossing) = exact_crossing(&h1, &h2) else {
+            panic!("The paths cross at (7, 0)");
+        };
+        assert_eq!(crossing.x_num, 7 * crossing.den);
+        assert_eq!(crossing.y_num, 0);
+        assert_eq!((crossing.t1_num, crossing.t2_num), (7 * crossing.den, 5 * crossing.den));
+
+        let area = |min_x, max_x, min_y, max_y| TestArea { min_x, max_x, min_y, max_y };
+        assert!(crossing.is_within(&area(7, 27, 0, 10)));
+        assert!(crossing.is_within(&area(-3, 7, -10, 0)));
+        assert!(!crossing.is_within(&area(8, 27, 0, 10)));
+        assert!(!crossing.is_within(&area(7, 27, 1, 10)));
+        assert!(hailstones_intersects_within_exact(&h1, &h2, &area(7, 27, 0, 10)));
+        assert!(!hailstones_intersects_within_exact(&h1, &h2, &area(0, 6, 0, 10)));
+    }
+}
@@ -0,0 +1,148 @@
+use itertools::Itertools;
+use rayon::prelude::*;
+use aoc_common::{Answer, AocError, Options, Solution};
+
+extern crate nalgebra as na;
+use na::{SMatrix, Vector3, Vector6};
+
+pub mod bench;
+pub mod chords;
+pub mod generate;
+pub mod intersection;
+pub mod parse;
+pub mod report;
+pub mod simulate;
+pub mod trajectory;
+pub mod vec3;
+use intersection::{hailstones_intersects_within_exact, TestArea};
+use parse::hailstones;
+use trajectory::{meeting_time, Vec3i};
+use vec3::Vec3;
+
+/** Structs */
+
+pub type Point = Vec3<i64>;
+pub type Vector = Vec3<i64>;
+
+#[derive(Debug)]
+pub struct Hailstone {
+    pub p: Point,
+    pub v: Vector,
+}
+
+// Hailstones and the area part 1 counts crossings in, which the input text does not give
+pub struct Puzzle {
+    pub hailstones: Vec<Hailstone>,
+    pub test_area: TestArea,
+}
+
+/** Functions */
+
+// Brute force over every pair
+pub fn count_intersections(hailstones: &[Hailstone], test_area: &TestArea) -> usize {
+    hailstones.iter().combinations(2)
+        .map(|vpair| hailstones_intersects_within_exact(vpair.first().unwrap(), vpair.last().unwrap(), test_area))
+        .filter(|x| *x)
+        .count()
+}
+
+// Same pairs as the brute force, with each outer hailstone and the ones after it handled by a
+// rayon task. Later outer indices have fewer pairs, which work stealing evens out.
+pub fn count_intersections_parallel(hailstones: &[Hailstone], test_area: &TestArea) -> usize {
+    (0..hailstones.len()).into_par_iter()
+        .map(|i| {
+            hailstones[i + 1..].iter()
+                .filter(|h2| hailstones_intersects_within_exact(&hailstones[i], h2, test_area))
+                .count()
+        })
+        .sum()
+}
+
+type Matrix6x6f = SMatrix<f64, 6, 6>;
+// Exact check that a rock thrown from p with velocity v hits the hailstone at some t >= 0
+fn rock_hits(p: &Vec3i, v: &Vec3i, h: &Hailstone) -> bool {
+    let dp = h.position_exact() - *p;
+    let dv = *v - h.velocity_exact();
+
+    // p + v*t = hp + hv*t  <=>  dp = dv*t
+    meeting_time(&dp, &dv).is_some()
+}
+
+// Rows of the linear system for the pair (h0, h1). For every hailstone
+//   (P - p_i) x (V - v_i) = 0
+// and subtracting two of these cancels the non-linear P x V term, leaving
+//   P x (v_1 - v_0) + (p_1 - p_0) x V = p_1 x v_1 - p_0 x v_0
+// Positions are taken relative to h0 to keep the magnitudes down.
+fn rock_equations(h0: &Hailstone, h1: &Hailstone) -> ([[f64; 6]; 3], [f64; 3]) {
+    let a = (h1.v - h0.v).to_f64();
+    let b = (h1.p - h0.p).to_f64();
+    let rhs = b.cross(&h1.v.to_f64());
+
+    let rows = [
+        [0.0, a.z, -a.y, 0.0, -b.z, b.y],
+        [-a.z, 0.0, a.x, b.z, 0.0, -b.x],
+        [a.y, -a.x, 0.0, -b.y, b.x, 0.0],
+    ];
+    (rows, [rhs.x, rhs.y, rhs.z])
+}
+
+// Solve for the rock position and velocity from three hailstones, then verify the
+// rounded solution against every hailstone with exact integer math
+pub fn solve_rock(hailstones: &[Hailstone]) -> Option<(Vec3i, Vec3i)> {
+    for window in hailstones.windows(3) {
+        let (h0, h1, h2) = (&window[0], &window[1], &window[2]);
+
+        let (rows_1, rhs_1) = rock_equations(h0, h1);
+        let (rows_2, rhs_2) = rock_equations(h0, h2);
+
+        let a = Matrix6x6f::from_fn(|r, c| if r < 3 { rows_1[r][c] } else { rows_2[r - 3][c] });
+        let b = Vector6::from_fn(|r, _| if r < 3 { rhs_1[r] } else { rhs_2[r - 3] });
+
+        let Some(x) = a.lu().solve(&b) else {
+            continue;
+        };
+
+        let round = |v: Vector3<f64>| Vec3::from(v).map(|c| c.round() as i128);
+        let p = h0.position_exact() + round(x.fixed_rows::<3>(0).into());
+        let v = round(x.fixed_rows::<3>(3).into());
+
+        if hailstones.iter().all(|h| rock_hits(&p, &v, h)) {
+            return Some((p, v));
+        }
+    }
+    None
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    const DEFAULT_INPUT: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    // Part 1 test area, e.g. test_area=7..27 for the example
+    const OPTIONS: &'static [&'static str] = &["test_area"];
+
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Puzzle, AocError> {
+        Ok(Puzzle { hailstones: hailstones(input)?, test_area: TestArea::PUZZLE })
+    }
+
+    fn parse_with(input: &str, options: &Options) -> Result<Puzzle, AocError> {
+        let mut puzzle = Self::parse(input)?;
+        if let Some(area) = options.get("test_area") {
+            puzzle.test_area = area.parse()?;
+        }
+        Ok(puzzle)
+    }
+
+    fn part1(puzzle: &Puzzle) -> Result<Answer, AocError> {
+        Ok(count_intersections(&puzzle.hailstones, &puzzle.test_area).into())
+    }
+
+    fn part2(puzzle: &Puzzle) -> Result<Answer, AocError> {
+        let (p, _) = solve_rock(&puzzle.hailstones)
+            .ok_or_else(|| AocError::NoSolution(String::from("no rock trajectory hits every hailstone")))?;
+        Ok((p.x + p.y + p.z).into())
+    }
+}
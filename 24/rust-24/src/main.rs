@@ -1,210 +1,220 @@
-use std::fs;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use clap::Parser;
 use itertools::Itertools;
 
-extern crate nalgebra as na;
-use na::{U2, SMatrix, Vector3, Vector2};
+use aoc_common::AocError;
 
-/** Structs */
+use rust_24::bench::bench_counters;
+use rust_24::chords::PairCounter;
+use rust_24::intersection::TestArea;
+use rust_24::parse::parse_hailstones;
+use rust_24::report::{write_report, IntersectionMethod, ReportFormat};
+use rust_24::simulate::{snapshot_times, write_snapshots, Rock};
+use rust_24::trajectory::PathRelation;
+use rust_24::{solve_rock, Hailstone};
 
-#[derive(Debug)]
-struct Point {
-    x: f64,
-    y: f64,
-    z: f64,
-}
-
-impl PartialEq for Point {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y && self.z == other.z
-    }
-}
+/** Functions */
 
-#[derive(Debug)]
-struct Vector {
-    x: f64,
-    y: f64,
-    z: f64,
-}
+fn part1(hailstones: &[Hailstone], args: &Args) -> Result<(), AocError> {
 
-impl Vector {
-    const fn new(x: f64, y: f64, z: f64) -> Vector {
-        Vector {x, y, z}
-    }
-}
+    let test_area = args.test_area();
 
+    // dbg!(&hailstones);
 
-#[derive(Debug)]
-struct Hailstone {
-    p: Point,
-    v: Vector,
-}
+    let intersctions = args.counter.count(hailstones, &test_area);
 
-/** Functions */
+    println!("Part 1 intersections: {}", intersctions);
 
-fn words_to_hailstone(words: Vec<String>) -> Hailstone {
-    let p = Point {
-        x: words[0].parse::<f64>().unwrap(),
-        y: words[1].parse::<f64>().unwrap(),
-        z: words[2].parse::<f64>().unwrap(),
-    };
-    let v = Vector {
-        x: words[4].parse::<f64>().unwrap(),
-        y: words[5].parse::<f64>().unwrap(),
-        z: words[6].parse::<f64>().unwrap(),
-    };
-    Hailstone {
-        p,
-        v,
+    if let Some(format) = args.report {
+        let result = match &args.report_file {
+            Some(path) => File::create(path)
+                .and_then(|f| write_report(&mut BufWriter::new(f), hailstones, args.report_method, format, &test_area))
+                .map_err(|e| AocError::io(path.display(), e)),
+            None => write_report(&mut io::stdout().lock(), hailstones, args.report_method, format, &test_area)
+                .map_err(|e| AocError::io("stdout", e)),
+        };
+        result?;
     }
+    Ok(())
 }
 
 
-fn parse_words(filename: &str) -> Vec<Vec<String>>{
-    let lines: Vec<Vec<String>> = fs::read_to_string(filename)
-        .expect("Unable to read file")
-        .split("\n")
-        .map(|line| line.trim())
-        .map(|line| line.split(" ")
-            .map(|sl| String::from(sl))
-            .collect::<Vec<_>>()
-        )
-        .collect::<Vec<_>>();
-    lines
-}
-
-// Solve system of 2 equations directly
-fn hailstones_intersects_witin(h1: &Hailstone, h2: &Hailstone, min: f64, max: f64) -> bool {
-
-    println!("Hailstone A: {}, {}, {} @ {}, {}, {}", h1.p.x, h1.p.y, h1.p.z, h1.v.x, h1.v.y, h1.v.z);
-    println!("Hailstone B: {}, {}, {} @ {}, {}, {}", h2.p.x, h2.p.y, h2.p.z, h2.v.x, h2.v.y, h2.v.z);
+fn part2(hailstones: &[Hailstone]) -> Result<(), AocError> {
 
-    let x1 = h1.p.x;
-    let y1 = h1.p.y;
-    
-    let x2 = h1.p.x + h1.v.x;
-    let y2 = h1.p.y + h1.v.y;
+    match solve_rock(hailstones) {
+        Some((p, v)) => {
+            println!("Rock: {}, {}, {} @ {}, {}, {}", p.x, p.y, p.z, v.x, v.y, v.z);
+            println!("Part 2 sum of coordinates: {}", p.x + p.y + p.z);
+            Ok(())
+        },
+        None => Err(AocError::NoSolution(String::from("no rock trajectory hits every hailstone"))),
+    }
+}
 
-    let x3 = h2.p.x;
-    let y3 = h2.p.y;
 
-    let x4 = h2.p.x + h2.v.x;
-    let y4 = h2.p.y + h2.v.y;
+// Look at the full 3D paths rather than their XY shadows
+fn trajectories(hailstones: &[Hailstone]) {
 
+    let mut crossing = 0;
+    let mut collinear = 0;
+    let mut collisions = 0;
+    let mut closest: Option<(usize, usize, f64)> = None;
 
-    let x_den = (x1 - x2) * (y3 - y4) - (y1 - y2) * (x3 - x4);
+    for ((i, h1), (j, h2)) in hailstones.iter().enumerate().tuple_combinations() {
+        match h1.path_relation(h2) {
+            PathRelation::Crossing { .. } => crossing += 1,
+            PathRelation::Collinear | PathRelation::Identical => collinear += 1,
+            _ => (),
+        }
+        if h1.collision_time(h2).is_some() {
+            collisions += 1;
+        }
 
-    if x_den == 0.0 {
-        println!("Hailstones' paths are parallel; they never intersect.");
-        return false;
+        let (_, _, distance) = h1.closest_approach(h2);
+        if closest.is_none_or(|(_, _, d)| distance < d) {
+            closest = Some((i, j, distance));
+        }
     }
 
-    let x_nom = (x1*y2 - y1*x2) * (x3 - x4) - (x1 - x2) * (x3*y4 - y3*x4);
-
-    let y_nom = (x1*y2 - y1*x2) * (y3 - y4) - (y1 - y2) * (x3*y4 - y3*x4);
-    let y_den = (x1 - x2) * (y3 - y4) - (y1 - y2) * (x3 - x4);
-
-    let x = x_nom / x_den;
-    let y = y_nom / y_den;
-    println!("Hailstones' paths will cross inside the test area (at x={}, y={})\n", x, y);
-
-    x > min && x < max && y > min && y < max
+    println!("3D crossing paths: {}, collinear paths: {}, collisions: {}", crossing, collinear, collisions);
+    if let Some((i, j, distance)) = closest {
+        println!("Closest paths: hailstones {} and {} at distance {}", i, j, distance);
+    }
 }
 
+// Positions of the hailstorm, and optionally the rock from part 2, at the requested times
+fn snapshots(hailstones: &[Hailstone], args: &Args, path: &Path) -> Result<(), AocError> {
 
+    let rock = if args.snapshot_rock {
+        let rock = solve_rock(hailstones).map(|(p, v)| Rock { p, v });
+        if rock.is_none() {
+            println!("Snapshots: no rock trajectory hits every hailstone, leaving it out");
+        }
+        rock
+    } else {
+        None
+    };
 
-type Matrix2x2f = SMatrix<f64, 2, 2>;
-
-// Solve system of 2 equations using LU decomposition
-fn hailstones_intersects_witin_2(h1: &Hailstone, h2: &Hailstone, min: f64, max: f64) -> bool {
+    let start = args.snapshot_time;
+    let times = snapshot_times(start, args.snapshot_end.unwrap_or(start), args.snapshot_count);
+    write_snapshots(path, hailstones, rock.as_ref(), &times).map_err(|e| AocError::io(path.display(), e))?;
+    println!("Wrote {} snapshot(s) to {}", times.len(), path.display());
+    Ok(())
+}
 
-    println!("Hailstone A: {}, {}, {} @ {}, {}, {}", h1.p.x, h1.p.y, h1.p.z, h1.v.x, h1.v.y, h1.v.z);
-    println!("Hailstone B: {}, {}, {} @ {}, {}, {}", h2.p.x, h2.p.y, h2.p.z, h2.v.x, h2.v.y, h2.v.z);
 
-    // parametric form 
+#[derive(Parser)]
+struct Args {
+    /// Hailstone list to read
+    #[arg(long, default_value = "input.txt")]
+    input: PathBuf,
 
-    // 1: p1.x + v1.x * t1 = p2.x + v2.x * t2
-    // 2: p1.y + v1.y * t1 = p2.y + v2.y * t2
+    /// Part to run, both when left out
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
 
-    // 1: t1*v1.x - t2*v2.x = p2.x - p1.x
-    // 2: t1*v1.y - t2*v2.y = p2.y - p1.y
+    /// Lower bound of the part 1 test area on both axes (7 for example.txt)
+    #[arg(long, default_value_t = 200000000000000, allow_negative_numbers = true)]
+    min: i64,
 
-    let a = Matrix2x2f::new( 
-        h1.v.x , -h2.v.x,
-        h1.v.y, -h2.v.y
-    );
+    /// Upper bound of the part 1 test area on both axes (27 for example.txt)
+    #[arg(long, default_value_t = 400000000000000, allow_negative_numbers = true)]
+    max: i64,
 
-    let b = Vector2::new(
-        h2.p.x - h1.p.x,
-        h2.p.y - h1.p.y,
-    );
+    /// Lower x bound, overrides --min
+    #[arg(long, allow_negative_numbers = true)]
+    min_x: Option<i64>,
 
-    let decomp = a.lu();
-    let t = decomp.solve(&b);
+    /// Upper x bound, overrides --max
+    #[arg(long, allow_negative_numbers = true)]
+    max_x: Option<i64>,
 
-    // return
-    return if let Some(t) = t {
+    /// Lower y bound, overrides --min
+    #[arg(long, allow_negative_numbers = true)]
+    min_y: Option<i64>,
 
+    /// Upper y bound, overrides --max
+    #[arg(long, allow_negative_numbers = true)]
+    max_y: Option<i64>,
 
-        let x = h1.p.x + h1.v.x * t[0];
-        let y = h1.p.y + h1.v.y * t[0];
+    /// Pair counting implementation for part 1
+    #[arg(long, value_enum, default_value_t = PairCounter::Brute)]
+    counter: PairCounter,
 
-        println!("Hailstones' paths will cross (at x={}, y={})", x, y);
-        println!("  (at t1={}, t2={})", t[0], t[1]);
+    /// Time every part 1 counter over this many runs instead of solving
+    #[arg(long)]
+    bench: Option<usize>,
 
-        if t[0] < 0.0 {
-            println!("Hailstones A intersects in the past");
-            return false;
-        }
-        if t[1] < 0.0 {
-            println!("Hailstones B intersects in the past");
-            return false;
-        }
+    /// Also compare the full 3D paths of every pair
+    #[arg(long)]
+    trajectories: bool,
 
-        let valid = x > min && x < max && y > min && y < max;
-        println!("  inside: {}", valid);
+    /// Write the hailstone positions to a .csv, .obj or .ply file
+    #[arg(long)]
+    snapshot: Option<PathBuf>,
 
-        println!("");
-        valid
-    } else  {
-        false
-    }
-}
+    /// Time of the (first) snapshot
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    snapshot_time: f64,
 
+    /// Time of the last snapshot, when writing several
+    #[arg(long, allow_negative_numbers = true)]
+    snapshot_end: Option<f64>,
 
-fn part1() {
+    /// Number of evenly spaced snapshots, numbered file_000.ext and up when more than one
+    #[arg(long, default_value_t = 1)]
+    snapshot_count: usize,
 
-    let filename = "input.txt";
-    let test_area_min: f64 = 200000000000000.0;
-    let test_area_max: f64 = 400000000000000.0;
+    /// Add the part 2 rock and its trajectory to the snapshots
+    #[arg(long)]
+    snapshot_rock: bool,
 
-    // let filename = "example.txt";
-    // let test_area_min: f64 = 7.0;
-    // let test_area_max: f64 = 27.0;
+    /// Write a record for every pair of hailstones in part 1
+    #[arg(long, value_enum)]
+    report: Option<ReportFormat>,
 
-    let lines = parse_words(filename);
-    let stripped_lines = lines.iter()
-        .map(|words| words.iter()
-            .map(|word| word.to_string().replace(",", ""))
-            .filter(|word| word.len() > 0)
-            .collect::<Vec<_>>()
-        )
-        .collect::<Vec<_>>();
+    /// Intersection implementation used for the report
+    #[arg(long, value_enum, default_value_t = IntersectionMethod::Exact)]
+    report_method: IntersectionMethod,
 
-    let hailstones: Vec<Hailstone> = stripped_lines.iter()
-        .map(|words| words_to_hailstone(words.to_vec()))
-        .collect::<Vec<_>>();
+    /// Write the report to a file instead of stdout
+    #[arg(long)]
+    report_file: Option<PathBuf>,
+}
 
-    // dbg!(&hailstones);
+impl Args {
+    fn test_area(&self) -> TestArea {
+        TestArea {
+            min_x: self.min_x.unwrap_or(self.min).into(),
+            max_x: self.max_x.unwrap_or(self.max).into(),
+            min_y: self.min_y.unwrap_or(self.min).into(),
+            max_y: self.max_y.unwrap_or(self.max).into(),
+        }
+    }
+}
 
-    let intersctions = hailstones.iter().combinations(2)
-        .map(|vpair| hailstones_intersects_witin_2(vpair.first().unwrap(), vpair.last().unwrap(), test_area_min, test_area_max))
-        .filter(|x| *x)
-        .count();
+fn main() -> Result<(), AocError> {
+    let args = Args::parse();
 
-    println!("Part 1 intersections: {}", intersctions);
-}
+    let hailstones = parse_hailstones(&args.input)?;
 
+    if let Some(runs) = args.bench {
+        return bench_counters(&hailstones, &args.test_area(), runs);
+    }
 
-fn main() {
-    part1();
+    if args.part.is_none_or(|p| p == 1) {
+        part1(&hailstones, &args)?;
+    }
+    if args.part.is_none_or(|p| p == 2) {
+        part2(&hailstones)?;
+    }
+    if args.trajectories {
+        trajectories(&hailstones);
+    }
+    if let Some(path) = &args.snapshot {
+        snapshots(&hailstones, &args, path)?;
+    }
+    Ok(())
 }
@@ -0,0 +1,134 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use aoc_common::parse::{self, ParseError, Span};
+use aoc_common::{read_input, AocError};
+
+use crate::{Hailstone, Point, Vector};
+
+/** Statics */
+
+// Largest position and velocity components accepted, in magnitude. The exact predicates
+// multiply up to three of them in i128, the largest being closest_approach's b*e - c*d at
+// about 18 * 2^(3*20 + 61) = 2^125.2, so anything beyond these bounds could overflow.
+// The real input stays below 2^49 and 2^10.
+pub const MAX_POSITION: i64 = 1 << 60;
+pub const MAX_VELOCITY: i64 = 1 << 20;
+
+/** Functions */
+
+// Three comma separated integers, with any whitespace around them, each at most `max` in magnitude
+fn parse_components(text: &Span, name: &str, max: i64) -> Result<[i64; 3], ParseError> {
+    let words = text.split(',');
+    if words.len() != 3 {
+        return Err(text.error(format!("expected 3 components, found {}", words.len())));
+    }
+
+    let mut components = [0; 3];
+    for (c, word) in components.iter_mut().zip(words) {
+        *c = word.parse::<i64>()?;
+        if c.unsigned_abs() > max.unsigned_abs() {
+            return Err(word.error(format!("{} {} is out of range, at most {} in magnitude", name, c, max)));
+        }
+    }
+    Ok(components)
+}
+
+// "px, py, pz @ vx, vy, vz"
+fn parse_hailstone(line: &Span) -> Result<Hailstone, ParseError> {
+    let halves = line.split('@');
+    let [position, velocity] = halves[..] else {
+        return Err(line.error("expected 'px, py, pz @ vx, vy, vz'"));
+    };
+    let [px, py, pz] = parse_components(&position, "position", MAX_POSITION)?;
+    let [vx, vy, vz] = parse_components(&velocity, "velocity", MAX_VELOCITY)?;
+
+    Ok(Hailstone {
+        p: Point { x: px, y: py, z: pz },
+        v: Vector::new(vx, vy, vz),
+    })
+}
+
+impl FromStr for Hailstone {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Hailstone, ParseError> {
+        parse_hailstone(&Span { line: 1, column: 1, text: line.trim() })
+    }
+}
+
+// One hailstone per line, blank lines are skipped
+pub fn parse_hailstones(filename: &Path) -> Result<Vec<Hailstone>, AocError> {
+    let input = read_input(filename)?;
+
+    Ok(hailstones_from_str(&input, &filename.display().to_string())?)
+}
+
+// Same as parse_hailstones for input that is already in memory, errors name it `filename`
+pub fn hailstones_from_str(input: &str, filename: &str) -> Result<Vec<Hailstone>, ParseError> {
+    hailstones(input).map_err(|e| e.in_file(filename))
+}
+
+// Errors only give the line and column, for callers that report the file themselves
+pub fn hailstones(input: &str) -> Result<Vec<Hailstone>, ParseError> {
+    parse::lines(input).iter().map(parse_hailstone).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intersection::{hailstones_intersection_exact, hailstones_intersects_within_exact, TestArea};
+
+    #[test]
+    fn spacing_around_separators_is_free() {
+        let expected = "19, 13, 30 @ -2, 1, -2".parse::<Hailstone>().unwrap();
+        for line in ["19,13,30@-2,1,-2", "  19 ,13,   30 @  -2,\t1, -2  ", "19, 13, 30 @ -2,  1, -2"] {
+            let h = line.parse::<Hailstone>().unwrap();
+            assert_eq!((h.p, h.v), (expected.p, expected.v), "{}", line);
+        }
+    }
+
+    #[test]
+    fn malformed_lines_are_located() {
+        let cases = [
+            ("1, 2, 3, 4, 5, 6", 1, "expected 'px, py, pz @ vx, vy, vz'"),
+            ("1, 2 @ 3, 4, 5", 1, "expected 3 components, found 2"),
+            ("1, 2, 3 @ 4, 5, 6, 7", 11, "expected 3 components, found 4"),
+            ("1, 2.5, 3 @ 4, 5, 6", 4, "invalid i64 '2.5'"),
+            ("0, 0, 0 @ 3000000000000000000, 1, 0", 11, "velocity 3000000000000000000 is out of range, at most 1048576 in magnitude"),
+            ("1, -1152921504606846977, 3 @ 4, 5, 6", 4, "position -1152921504606846977 is out of range, at most 1152921504606846976 in magnitude"),
+        ];
+        for (line, column, message) in cases {
+            assert_eq!(hailstones(line).err(), Some(ParseError::new(1, column, message)), "{}", line);
+        }
+    }
+
+    // Every exact routine on the largest accepted magnitudes, which would panic on overflow
+    #[test]
+    fn largest_accepted_magnitudes_do_not_overflow() {
+        let (p, v) = (MAX_POSITION, MAX_VELOCITY);
+        let hailstones = hailstones(&format!(
+            "{p}, -{p}, {p} @ {v}, -{v}, 1\n-{p}, {p}, -{p} @ -{v}, -{v}, {v}\n{p}, {p}, 0 @ -1, {v}, -{v}"
+        ))
+        .unwrap();
+        let area = TestArea::square(i64::MIN as i128, i64::MAX as i128);
+
+        for h1 in &hailstones {
+            for h2 in &hailstones {
+                let _ = hailstones_intersection_exact(h1, h2);
+                let _ = hailstones_intersects_within_exact(h1, h2, &area);
+                let _ = h1.path_relation(h2);
+                let _ = h1.closest_approach(h2);
+                let _ = h1.collision_time(h2);
+            }
+        }
+    }
+
+    #[test]
+    fn errors_name_the_file_line_and_column() {
+        let input = "1, 2, 3 @ 4, 5, 6\n\n  7, 8, x @ 1, 1, 1\n";
+        let error = hailstones_from_str(input, "storm.txt").err().unwrap();
+
+        assert_eq!(error.to_string(), "storm.txt:3:9: invalid i64 'x'");
+    }
+}
@@ -0,0 +1,172 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::intersection::{
+    hailstones_intersection, hailstones_intersection_2, hailstones_intersection_exact,
+    hailstones_intersects_within_exact, Intersection, TestArea,
+};
+use crate::Hailstone;
+
+/** Structs */
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum IntersectionMethod {
+    /// Closed form line intersection in f64
+    Direct,
+    /// LU decomposition of the 2x2 system in f64
+    Lu,
+    /// Cramer's rule in i128
+    Exact,
+}
+
+impl IntersectionMethod {
+    pub fn intersection(&self, h1: &Hailstone, h2: &Hailstone) -> Intersection {
+        match self {
+            IntersectionMethod::Direct => hailstones_intersection(h1, h2),
+            IntersectionMethod::Lu => hailstones_intersection_2(h1, h2),
+            IntersectionMethod::Exact => hailstones_intersection_exact(h1, h2),
+        }
+    }
+
+    pub fn is_within(&self, h1: &Hailstone, h2: &Hailstone, area: &TestArea) -> bool {
+        match self {
+            IntersectionMethod::Exact => hailstones_intersects_within_exact(h1, h2, area),
+            _ => self.intersection(h1, h2).is_within(area),
+        }
+    }
+}
+
+// One line of the report. Missing values are empty in CSV and null in JSON, and serde_json
+// writes null for the infinite or NaN values the f64 methods can produce.
+#[derive(Debug, Serialize)]
+struct PairRecord {
+    a: usize,
+    b: usize,
+    result: &'static str,
+    x: Option<f64>,
+    y: Option<f64>,
+    t1: Option<f64>,
+    t2: Option<f64>,
+    inside: bool,
+}
+
+/** Functions */
+
+fn result_name(intersection: &Intersection) -> &'static str {
+    match intersection {
+        Intersection::Parallel => "parallel",
+        Intersection::InPastA => "past_a",
+        Intersection::InPastB => "past_b",
+        Intersection::Crosses { .. } => "crosses",
+    }
+}
+
+fn csv_number(value: Option<f64>) -> String {
+    value.map_or(String::new(), |v| v.to_string())
+}
+
+impl PairRecord {
+    fn new(a: usize, b: usize, intersection: Intersection, inside: bool) -> PairRecord {
+        let [x, y, t1, t2] = match intersection {
+            Intersection::Crosses { x, y, t1, t2 } => [Some(x), Some(y), Some(t1), Some(t2)],
+            _ => [None; 4],
+        };
+        PairRecord { a, b, result: result_name(&intersection), x, y, t1, t2, inside }
+    }
+
+    fn write_csv<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let [x, y, t1, t2] = [self.x, self.y, self.t1, self.t2].map(csv_number);
+        writeln!(out, "{},{},{},{},{},{},{},{}", self.a, self.b, self.result, x, y, t1, t2, self.inside)
+    }
+}
+
+// One record per pair of hailstones: indices, result, crossing point, times and
+// whether the crossing counts towards part 1
+pub fn write_report<W: Write>(
+    out: &mut W,
+    hailstones: &[Hailstone],
+    method: IntersectionMethod,
+    format: ReportFormat,
+    area: &TestArea,
+) -> io::Result<()> {
+    match format {
+        ReportFormat::Csv => writeln!(out, "a,b,result,x,y,t1,t2,inside")?,
+        ReportFormat::Json => write!(out, "[")?,
+    }
+
+    let pairs = hailstones.iter().enumerate().tuple_combinations::<(_, _)>();
+    for (n, ((a, h1), (b, h2))) in pairs.enumerate() {
+        let record = PairRecord::new(a, b, method.intersection(h1, h2), method.is_within(h1, h2, area));
+
+        match format {
+            ReportFormat::Csv => record.write_csv(out)?,
+            ReportFormat::Json => {
+                write!(out, "{}\n  ", if n == 0 { "" } else { "," })?;
+                serde_json::to_writer(&mut *out, &record)?;
+            }
+        }
+    }
+
+    if format == ReportFormat::Json {
+        writeln!(out, "\n]")?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::hailstones;
+
+    // First three example hailstones: 0 and 1 as well as 0 and 2 cross inside, 1 and 2 are parallel
+    fn report(format: ReportFormat) -> String {
+        let text = "19, 13, 30 @ -2, 1, -2\n18, 19, 22 @ -1, -1, -2\n20, 25, 34 @ -2, -2, -4";
+        let area = TestArea::square(7, 27);
+        let mut out = Vec::new();
+        write_report(&mut out, &hailstones(text).unwrap(), IntersectionMethod::Exact, format, &area).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn csv_has_a_row_per_pair() {
+        let csv = report(ReportFormat::Csv);
+        let rows = csv.lines().collect::<Vec<_>>();
+
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0], "a,b,result,x,y,t1,t2,inside");
+        assert!(rows[1].starts_with("0,1,crosses,14.333") && rows[1].ends_with(",true"), "{}", rows[1]);
+        assert_eq!(rows[3], "1,2,parallel,,,,,false");
+    }
+
+    #[test]
+    fn json_is_an_array_of_records() {
+        let json = serde_json::from_str::<serde_json::Value>(&report(ReportFormat::Json)).unwrap();
+        let records = json.as_array().unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1]["a"], 0);
+        assert_eq!(records[1]["b"], 2);
+        assert_eq!(records[1]["result"], "crosses");
+        assert_eq!(records[1]["inside"], true);
+        assert_eq!(records[2]["result"], "parallel");
+        assert!(records[2]["x"].is_null());
+
+    }
+
+    #[test]
+    fn non_finite_values_stay_valid_json() {
+        let crossing = Intersection::Crosses { x: f64::INFINITY, y: f64::NAN, t1: 1.0, t2: f64::NEG_INFINITY };
+        let json = serde_json::to_string(&PairRecord::new(0, 1, crossing, false)).unwrap();
+
+        assert_eq!(json, r#"{"a":0,"b":1,"result":"crosses","x":null,"y":null,"t1":1.0,"t2":null,"inside":false}"#);
+    }
+}
@@ -0,0 +1,236 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::trajectory::{meeting_time, Vec3i};
+use crate::vec3::Vec3;
+use crate::Hailstone;
+
+// Snapshots of the hailstorm at a given time, for looking at it in a spreadsheet or a 3D
+// viewer. Positions are computed and written as f64, which is only exact for integer times
+// while p + v*t stays below 2^53 (about 9e15). Real input coordinates are around 4e14, so
+// fractional times, or times far enough out to pass 2^53, round to the nearest f64, which
+// is within a few units at that magnitude.
+
+/** Structs */
+
+// Thrown rock, as found by part 2
+pub struct Rock {
+    pub p: Vec3i,
+    pub v: Vec3i,
+}
+
+struct Snapshot<'a> {
+    hailstones: &'a [Hailstone],
+    rock: Option<&'a Rock>,
+    t: f64,
+}
+
+/** Statics */
+
+const HAILSTONE_COLOR: [u8; 3] = [160, 200, 255];
+const ROCK_COLOR: [u8; 3] = [255, 80, 40];
+
+/** Functions */
+
+impl Hailstone {
+    pub fn position_at(&self, t: f64) -> Vec3<f64> {
+        self.p.to_f64() + self.v.to_f64() * t
+    }
+}
+
+impl Rock {
+    pub fn position_at(&self, t: f64) -> Vec3<f64> {
+        self.p.to_f64() + self.v.to_f64() * t
+    }
+
+    // Time at which the rock hits each hailstone, None for the ones it misses
+    pub fn hit_times(&self, hailstones: &[Hailstone]) -> Vec<Option<f64>> {
+        hailstones
+            .iter()
+            .map(|h| {
+                let dp = h.position_exact() - self.p;
+                let dv = self.v - h.velocity_exact();
+                meeting_time(&dp, &dv).map(|(num, den)| num as f64 / den as f64)
+            })
+            .collect()
+    }
+
+    // Trajectory from the throw up to the last hailstone it hits
+    fn trajectory(&self, hailstones: &[Hailstone]) -> (Vec3<f64>, Vec3<f64>) {
+        let last_hit = self.hit_times(hailstones).into_iter().flatten().fold(0.0, f64::max);
+        (self.position_at(0.0), self.position_at(last_hit))
+    }
+}
+
+// `count` evenly spaced times from start to end, both included
+pub fn snapshot_times(start: f64, end: f64, count: usize) -> Vec<f64> {
+    if count <= 1 {
+        return vec![start];
+    }
+    (0..count)
+        .map(|i| start + (end - start) * i as f64 / (count - 1) as f64)
+        .collect()
+}
+
+// "storm.ply" becomes "storm_003.ply" when more than one snapshot is written
+fn numbered_path(path: &Path, index: usize, count: usize) -> PathBuf {
+    if count <= 1 {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("snapshot");
+    let mut name = format!("{}_{:03}", stem, index);
+    if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
+        name = format!("{}.{}", name, extension);
+    }
+    path.with_file_name(name)
+}
+
+impl Snapshot<'_> {
+    // kind,index,t,x,y,z with one row per hailstone, then the rock and its hit time if any
+    fn write_csv<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "kind,index,t,x,y,z,hit_t")?;
+
+        let hits = self.rock.map(|r| r.hit_times(self.hailstones));
+        for (i, h) in self.hailstones.iter().enumerate() {
+            let Vec3 { x, y, z } = h.position_at(self.t);
+            let hit = hits.as_ref().and_then(|hits| hits[i]).map_or(String::new(), |t| t.to_string());
+            writeln!(out, "hailstone,{},{},{},{},{},{}", i, self.t, x, y, z, hit)?;
+        }
+        if let Some(rock) = self.rock {
+            let Vec3 { x, y, z } = rock.position_at(self.t);
+            writeln!(out, "rock,,{},{},{},{},", self.t, x, y, z)?;
+        }
+        Ok(())
+    }
+
+    // Points for every hailstone and the rock, plus a line for the rock trajectory
+    fn write_obj<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "# hailstorm at t = {}", self.t)?;
+        writeln!(out, "o hailstones")?;
+        for h in self.hailstones {
+            let Vec3 { x, y, z } = h.position_at(self.t);
+            writeln!(out, "v {} {} {}", x, y, z)?;
+        }
+        for i in 1..=self.hailstones.len() {
+            writeln!(out, "p {}", i)?;
+        }
+
+        if let Some(rock) = self.rock {
+            let base = self.hailstones.len();
+            let Vec3 { x, y, z } = rock.position_at(self.t);
+            let (from, to) = rock.trajectory(self.hailstones);
+
+            writeln!(out, "o rock")?;
+            writeln!(out, "v {} {} {}", x, y, z)?;
+            writeln!(out, "p {}", base + 1)?;
+            writeln!(out, "o rock_trajectory")?;
+            writeln!(out, "v {} {} {}", from.x, from.y, from.z)?;
+            writeln!(out, "v {} {} {}", to.x, to.y, to.z)?;
+            writeln!(out, "l {} {}", base + 2, base + 3)?;
+        }
+        Ok(())
+    }
+
+    // ASCII PLY point cloud, with the rock in its own color and its trajectory as an edge
+    fn write_ply<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let mut vertices = self
+            .hailstones
+            .iter()
+            .map(|h| (h.position_at(self.t), HAILSTONE_COLOR))
+            .collect::<Vec<_>>();
+        let mut edges = Vec::new();
+
+        if let Some(rock) = self.rock {
+            let (from, to) = rock.trajectory(self.hailstones);
+            vertices.push((rock.position_at(self.t), ROCK_COLOR));
+            vertices.push((from, ROCK_COLOR));
+            vertices.push((to, ROCK_COLOR));
+            edges.push((vertices.len() - 2, vertices.len() - 1));
+        }
+
+        writeln!(out, "ply")?;
+        writeln!(out, "format ascii 1.0")?;
+        writeln!(out, "comment hailstorm at t = {}", self.t)?;
+        writeln!(out, "element vertex {}", vertices.len())?;
+        for property in ["double x", "double y", "double z", "uchar red", "uchar green", "uchar blue"] {
+            writeln!(out, "property {}", property)?;
+        }
+        writeln!(out, "element edge {}", edges.len())?;
+        writeln!(out, "property int vertex1")?;
+        writeln!(out, "property int vertex2")?;
+        writeln!(out, "end_header")?;
+
+        for (Vec3 { x, y, z }, [r, g, b]) in vertices {
+            writeln!(out, "{} {} {} {} {} {}", x, y, z, r, g, b)?;
+        }
+        for (a, b) in edges {
+            writeln!(out, "{} {}", a, b)?;
+        }
+        Ok(())
+    }
+
+    // Pick the format from the file extension, defaulting to CSV
+    fn write(&self, path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("obj") => self.write_obj(&mut out)?,
+            Some("ply") => self.write_ply(&mut out)?,
+            _ => self.write_csv(&mut out)?,
+        }
+        out.flush()
+    }
+}
+
+// Write one snapshot per time, numbering the files when there is more than one
+pub fn write_snapshots(path: &Path, hailstones: &[Hailstone], rock: Option<&Rock>, times: &[f64]) -> io::Result<()> {
+    for (i, t) in times.iter().enumerate() {
+        let snapshot = Snapshot { hailstones, rock, t: *t };
+        snapshot.write(&numbered_path(path, i, times.len()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Point, Vector};
+
+    #[test]
+    fn snapshot_times_are_evenly_spaced() {
+        assert_eq!(snapshot_times(0.0, 10.0, 3), vec![0.0, 5.0, 10.0]);
+        assert_eq!(snapshot_times(2.0, 1.0, 5), vec![2.0, 1.75, 1.5, 1.25, 1.0]);
+        assert_eq!(snapshot_times(4.0, 10.0, 1), vec![4.0]);
+        assert_eq!(snapshot_times(4.0, 10.0, 0), vec![4.0]);
+    }
+
+    #[test]
+    fn paths_are_numbered_when_writing_several() {
+        assert_eq!(numbered_path(Path::new("storm.ply"), 3, 1), PathBuf::from("storm.ply"));
+        assert_eq!(numbered_path(Path::new("storm.ply"), 3, 5), PathBuf::from("storm_003.ply"));
+        assert_eq!(numbered_path(Path::new("out/storm.csv"), 0, 2), PathBuf::from("out/storm_000.csv"));
+        assert_eq!(numbered_path(Path::new("storm"), 12, 20), PathBuf::from("storm_012"));
+    }
+
+    #[test]
+    fn positions_and_hit_times() {
+        let h = Hailstone { p: Point::new(4, -8, 1), v: Vector::new(0, 2, -1) };
+        assert_eq!(h.position_at(4.0), Vec3::new(4.0, 0.0, -3.0));
+        assert_eq!(h.position_at(-0.5), Vec3::new(4.0, -9.0, 1.5));
+
+        let rock = Rock { p: Vec3i::new(0, 0, -3), v: Vec3i::new(1, 0, 0) };
+        let missed = Hailstone { p: Point::new(0, 1, 0), v: Vector::new(1, 0, 0) };
+        assert_eq!(rock.position_at(4.0), Vec3::new(4.0, 0.0, -3.0));
+        assert_eq!(rock.hit_times(&[h, missed]), vec![Some(4.0), None]);
+    }
+
+    #[test]
+    fn positions_round_past_2_pow_53() {
+        let start = (1_i64 << 53) - 1;
+        let h = Hailstone { p: Point::new(start, 0, 0), v: Vector::new(1, 0, 0) };
+
+        assert_eq!(h.position_at(1.0).x, (1_i64 << 53) as f64);
+        // 2^53 + 1 has no f64, it rounds to an even neighbour
+        assert_eq!(h.position_at(2.0).x, (1_i64 << 53) as f64);
+    }
+}
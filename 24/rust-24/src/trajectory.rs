@@ -0,0 +1,186 @@
+use crate::vec3::Vec3;
+use crate::Hailstone;
+
+/** Integer vector math */
+
+pub type Vec3i = Vec3<i128>;
+
+fn norm3(a: &Vec3i) -> f64 {
+    a.to_f64().norm()
+}
+
+// Time t >= 0 at which dp = dv*t holds, as a fraction (numerator, denominator)
+// with a positive denominator. A zero dv only solves it if dp is zero too, at any time.
+pub fn meeting_time(dp: &Vec3i, dv: &Vec3i) -> Option<(i128, i128)> {
+    // Any non-zero component of dv gives the time once dp is known to be parallel to dv
+    let components = [(dp.x, dv.x), (dp.y, dv.y), (dp.z, dv.z)];
+    let Some((p, v)) = components.into_iter().find(|(_, v)| *v != 0) else {
+        return if dp.is_zero() { Some((0, 1)) } else { None };
+    };
+
+    if !dp.cross(dv).is_zero() {
+        return None;
+    }
+
+    let (num, den) = if v < 0 { (-p, -v) } else { (p, v) };
+    if num < 0 {
+        return None;
+    }
+    Some((num, den))
+}
+
+/** Structs */
+
+// How the paths of two hailstones lie relative to each other, ignoring timing
+#[derive(Debug, PartialEq)]
+pub enum PathRelation {
+    // Same position at every point in time
+    Identical,
+    // Different motion along the same line
+    Collinear,
+    Parallel,
+    // The paths meet, with each hailstone reaching the meeting point at its own time
+    Crossing { t1: f64, t2: f64 },
+    Skew,
+}
+
+impl Hailstone {
+    pub fn position_exact(&self) -> Vec3i {
+        self.p.to_i128()
+    }
+
+    pub fn velocity_exact(&self) -> Vec3i {
+        self.v.to_i128()
+    }
+
+    // Time t >= 0 at which both hailstones are at the same place at the same time
+    pub fn collision_time(&self, other: &Hailstone) -> Option<f64> {
+        let dp = other.position_exact() - self.position_exact();
+        let dv = self.velocity_exact() - other.velocity_exact();
+
+        meeting_time(&dp, &dv).map(|(num, den)| num as f64 / den as f64)
+    }
+
+    pub fn path_relation(&self, other: &Hailstone) -> PathRelation {
+        let (p1, v1) = (self.position_exact(), self.velocity_exact());
+        let (p2, v2) = (other.position_exact(), other.velocity_exact());
+        let w = p2 - p1;
+        let n = v1.cross(&v2);
+
+        if n.is_zero() {
+            return if w.is_zero() && v1 == v2 {
+                PathRelation::Identical
+            } else if w.cross(&v1).is_zero() && w.cross(&v2).is_zero() {
+                PathRelation::Collinear
+            } else {
+                PathRelation::Parallel
+            };
+        }
+
+        if w.dot(&n) != 0 {
+            return PathRelation::Skew;
+        }
+
+        let (t1, t2, _) = self.closest_approach(other);
+        PathRelation::Crossing { t1, t2 }
+    }
+
+    // Closest points of the two paths, p1 + v1*t1 and p2 + v2*t2, and the distance between them.
+    // Parallel paths are equally close everywhere, so t1 is pinned to 0.
+    pub fn closest_approach(&self, other: &Hailstone) -> (f64, f64, f64) {
+        let (p1, v1) = (self.position_exact(), self.velocity_exact());
+        let (p2, v2) = (other.position_exact(), other.velocity_exact());
+        let w = p1 - p2;
+
+        let a = v1.dot(&v1);
+        let b = v1.dot(&v2);
+        let c = v2.dot(&v2);
+        let d = v1.dot(&w);
+        let e = v2.dot(&w);
+        let n = v1.cross(&v2);
+        let den = a * c - b * b;
+
+        if den == 0 {
+            let t2 = if c == 0 { 0.0 } else { e as f64 / c as f64 };
+            let distance = if c == 0 {
+                norm3(&w)
+            } else {
+                norm3(&w.cross(&v2)) / norm3(&v2)
+            };
+            return (0.0, t2, distance);
+        }
+
+        let t1 = (b * e - c * d) as f64 / den as f64;
+        let t2 = (a * e - b * d) as f64 / den as f64;
+        let distance = (w.dot(&n) as f64).abs() / norm3(&n);
+        (t1, t2, distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Point, Vector};
+
+    fn hailstone(p: [i64; 3], v: [i64; 3]) -> Hailstone {
+        Hailstone { p: Point::new(p[0], p[1], p[2]), v: Vector::new(v[0], v[1], v[2]) }
+    }
+
+    #[test]
+    fn meeting_time_as_a_fraction() {
+        let v = |x, y, z| Vec3i::new(x, y, z);
+
+        assert_eq!(meeting_time(&v(3, 6, 0), &v(2, 4, 0)), Some((3, 2)));
+        assert_eq!(meeting_time(&v(-3, -6, 0), &v(-2, -4, 0)), Some((3, 2)));
+        // Only in the past, or never
+        assert_eq!(meeting_time(&v(-3, -6, 0), &v(2, 4, 0)), None);
+        assert_eq!(meeting_time(&v(3, 5, 0), &v(2, 4, 0)), None);
+        // Without relative motion they meet always or never
+        assert_eq!(meeting_time(&v(0, 0, 0), &v(0, 0, 0)), Some((0, 1)));
+        assert_eq!(meeting_time(&v(1, 0, 0), &v(0, 0, 0)), None);
+    }
+
+    #[test]
+    fn path_relations() {
+        let h = hailstone([0, 0, 0], [1, 0, 0]);
+        let cases = [
+            (hailstone([0, 0, 0], [1, 0, 0]), PathRelation::Identical),
+            (hailstone([5, 0, 0], [2, 0, 0]), PathRelation::Collinear),
+            (hailstone([0, 1, 0], [-3, 0, 0]), PathRelation::Parallel),
+            (hailstone([5, -3, 0], [0, 1, 0]), PathRelation::Crossing { t1: 5.0, t2: 3.0 }),
+            (hailstone([0, 0, 1], [0, 1, 0]), PathRelation::Skew),
+        ];
+        for (other, relation) in cases {
+            assert_eq!(h.path_relation(&other), relation, "{:?}", other);
+        }
+    }
+
+    #[test]
+    fn collision_needs_the_same_place_at_the_same_time() {
+        let h = hailstone([0, 0, 0], [1, 0, 0]);
+
+        // Both at (4, 0, 0) at t = 4
+        let colliding = hailstone([4, -8, 0], [0, 2, 0]);
+        assert_eq!(h.collision_time(&colliding), Some(4.0));
+        assert_eq!(h.path_relation(&colliding), PathRelation::Crossing { t1: 4.0, t2: 4.0 });
+
+        // Same meeting point, reached at t = 2
+        let crossing = hailstone([4, -4, 0], [0, 2, 0]);
+        assert_eq!(h.collision_time(&crossing), None);
+        assert_eq!(h.path_relation(&crossing), PathRelation::Crossing { t1: 4.0, t2: 2.0 });
+
+        // Together at (-4, 0, 0) at t = -4
+        assert_eq!(h.collision_time(&hailstone([-4, 8, 0], [0, 2, 0])), None);
+    }
+
+    #[test]
+    fn closest_approach_of_skew_and_parallel_paths() {
+        let h = hailstone([0, 0, 0], [1, 0, 0]);
+
+        let (t1, t2, distance) = h.closest_approach(&hailstone([3, -2, 1], [0, 1, 0]));
+        assert_eq!((t1, t2, distance), (3.0, 2.0, 1.0));
+
+        let (t1, t2, distance) = h.closest_approach(&hailstone([6, 3, 4], [2, 0, 0]));
+        assert_eq!((t1, t2, distance), (0.0, -3.0, 5.0));
+    }
+}
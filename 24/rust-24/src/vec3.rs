@@ -0,0 +1,143 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+extern crate nalgebra as na;
+use na::{Scalar, Vector3};
+
+// Three component vector shared by positions and velocities. The input is read as i64 and
+// exact predicates widen to i128, which only holds their products because the parser bounds
+// the magnitudes (see parse::MAX_POSITION). The floating point solvers work on f64.
+
+/** Structs */
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Vec3<T> {
+        Vec3 { x, y, z }
+    }
+
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Vec3<U> {
+        Vec3::new(f(self.x), f(self.y), f(self.z))
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Vec3<T> {
+    pub fn dot(&self, other: &Vec3<T>) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: &Vec3<T>) -> Vec3<T> {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+impl<T: Default + PartialEq> Vec3<T> {
+    pub fn is_zero(&self) -> bool {
+        *self == Vec3::default()
+    }
+}
+
+impl Vec3<i64> {
+    pub fn to_i128(self) -> Vec3<i128> {
+        self.map(i128::from)
+    }
+
+    pub fn to_f64(self) -> Vec3<f64> {
+        self.map(|c| c as f64)
+    }
+}
+
+impl Vec3<i128> {
+    pub fn to_f64(self) -> Vec3<f64> {
+        self.map(|c| c as f64)
+    }
+}
+
+impl Vec3<f64> {
+    pub fn norm(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+}
+
+/** Operators */
+
+impl<T: Add<Output = T>> Add for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn add(self, other: Vec3<T>) -> Vec3<T> {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn sub(self, other: Vec3<T>) -> Vec3<T> {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn neg(self) -> Vec3<T> {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+// Scaling by a scalar, e.g. a velocity times a time
+impl<T: Copy + Mul<Output = T>> Mul<T> for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn mul(self, k: T) -> Vec3<T> {
+        Vec3::new(self.x * k, self.y * k, self.z * k)
+    }
+}
+
+/** nalgebra interop */
+
+impl<T: Scalar> From<Vec3<T>> for Vector3<T> {
+    fn from(v: Vec3<T>) -> Vector3<T> {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl<T: Scalar> From<Vector3<T>> for Vec3<T> {
+    fn from(v: Vector3<T>) -> Vec3<T> {
+        Vec3::new(v.x.clone(), v.y.clone(), v.z.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators_match_nalgebra() {
+        let a = Vec3::new(3.0, -1.0, 2.0);
+        let b = Vec3::new(-4.0, 5.0, 0.5);
+        let (na_a, na_b) = (Vector3::from(a), Vector3::from(b));
+
+        assert_eq!(Vec3::from(na_a + na_b), a + b);
+        assert_eq!(Vec3::from(na_a - na_b), a - b);
+        assert_eq!(Vec3::from(na_a * 2.5), a * 2.5);
+        assert_eq!(Vec3::from(na_a.cross(&na_b)), a.cross(&b));
+        assert_eq!(na_a.dot(&na_b), a.dot(&b));
+    }
+
+    #[test]
+    fn widening_keeps_large_products_exact() {
+        let v = Vec3::new(i64::MAX, 1, -1).to_i128();
+        assert_eq!(v.dot(&v), i64::MAX as i128 * i64::MAX as i128 + 2);
+        assert!(v.cross(&v).is_zero());
+    }
+}
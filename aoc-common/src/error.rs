@@ -0,0 +1,80 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::parse::ParseError;
+
+// Everything that can go wrong while solving a day, from reading the input to finding out
+// that it has no answer.
+
+/** Structs */
+
+pub enum AocError {
+    // Reading or writing a file, with the path or whatever else was being accessed
+    Io { context: String, error: io::Error },
+    // Input that does not have the expected format
+    Parse(ParseError),
+    // Well formed input the puzzle rules out, like a dig plan that crosses itself
+    InvalidInput(String),
+    // Valid input without an answer, like a hailstorm no single rock can hit
+    NoSolution(String),
+    // Two ways of computing the same result that disagree, a bug rather than a bad input
+    Mismatch(String),
+}
+
+impl AocError {
+    pub fn io(context: impl fmt::Display, error: io::Error) -> AocError {
+        AocError::Io { context: context.to_string(), error }
+    }
+
+    // Name the file a parse error was found in, other errors are left alone
+    pub fn in_file(self, file: impl Into<String>) -> AocError {
+        match self {
+            AocError::Parse(e) => AocError::Parse(e.in_file(file)),
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Io { context, error } => write!(f, "{}: {}", context, error),
+            AocError::Parse(e) => write!(f, "{}", e),
+            AocError::InvalidInput(message) => write!(f, "invalid input: {}", message),
+            AocError::NoSolution(message) => write!(f, "no solution: {}", message),
+            AocError::Mismatch(message) => write!(f, "mismatch: {}", message),
+        }
+    }
+}
+
+// A main returning Err prints the Debug form, which should read like the Display one
+impl fmt::Debug for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io { error, .. } => Some(error),
+            AocError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for AocError {
+    fn from(error: ParseError) -> AocError {
+        AocError::Parse(error)
+    }
+}
+
+/** Functions */
+
+pub fn read_input(path: impl AsRef<Path>) -> Result<String, AocError> {
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(|e| AocError::io(path.display(), e))
+}
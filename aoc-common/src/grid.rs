@@ -0,0 +1,225 @@
+use std::fmt;
+
+use crate::parse::ParseError;
+
+// Rectangular grid stored row by row in one Vec. (0, 0) is the first character of the
+// first line when parsed from text, x grows to the right and y grows downwards.
+
+/** Structs */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GridError {
+    // Row (zero based) with a different length than the first one
+    RaggedRow { row: usize, expected: usize, found: usize },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::RaggedRow { row, expected, found } => {
+                write!(f, "row {} has {} cells, expected {}", row + 1, found, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+// Rows are the lines of the parsed text, the column is where the short row ends or the long one goes on
+impl From<GridError> for ParseError {
+    fn from(error: GridError) -> ParseError {
+        match error {
+            GridError::RaggedRow { row, expected, found } => {
+                ParseError::new(row + 1, expected.min(found) + 1, error.to_string())
+            }
+        }
+    }
+}
+
+/** Statics */
+
+pub const OFFSETS_4: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+pub const OFFSETS_8: [(isize, isize); 8] = [
+    (1, 0), (1, 1), (0, 1), (-1, 1),
+    (-1, 0), (-1, -1), (0, -1), (1, -1),
+];
+
+/** Functions */
+
+// (x + dx, y + dy) if that is still inside a width x height area
+pub fn step(x: usize, y: usize, (dx, dy): (isize, isize), width: usize, height: usize) -> Option<(usize, usize)> {
+    let xx = x.checked_add_signed(dx).filter(|xx| *xx < width)?;
+    let yy = y.checked_add_signed(dy).filter(|yy| *yy < height)?;
+    Some((xx, yy))
+}
+
+// Neighbours of (x, y) inside a width x height area, for grids that keep their own storage
+pub fn neighbors4(x: usize, y: usize, width: usize, height: usize) -> impl Iterator<Item = (usize, usize)> {
+    OFFSETS_4.into_iter().filter_map(move |d| step(x, y, d, width, height))
+}
+
+pub fn neighbors8(x: usize, y: usize, width: usize, height: usize) -> impl Iterator<Item = (usize, usize)> {
+    OFFSETS_8.into_iter().filter_map(move |d| step(x, y, d, width, height))
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    // Cells in row order, one row after the other
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), width * height, "Grid needs width * height cells");
+        Grid { width, height, cells }
+    }
+
+    // One row per line and one cell per character. Trailing whitespace and empty
+    // lines at the end are ignored, every other line has to be as long as the first.
+    pub fn parse(text: &str, mut cell: impl FnMut(char) -> T) -> Result<Grid<T>, GridError> {
+        let lines = text.trim_end().lines().map(|l| l.trim_end()).collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |l| l.chars().count());
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            let found = line.chars().count();
+            if found != width {
+                return Err(GridError::RaggedRow { row, expected: width, found });
+            }
+            cells.extend(line.chars().map(&mut cell));
+        }
+
+        Ok(Grid { width, height: lines.len(), cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if self.contains(x, y) {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    // Neighbouring position in direction (dx, dy), None past the edge
+    pub fn step(&self, x: usize, y: usize, offset: (isize, isize)) -> Option<(usize, usize)> {
+        step(x, y, offset, self.width, self.height)
+    }
+
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        neighbors4(x, y, self.width, self.height)
+    }
+
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        neighbors8(x, y, self.width, self.height)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    // Every position with its cell, in row order
+    pub fn positions(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(i, c)| ((i % width, i / width), c))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+// One line per row, cells written next to each other
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        let text = "#..\n.#.\n..#\n";
+        let grid = Grid::parse(text, |c| c).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.get(1, 1), Some(&'#'));
+        assert_eq!(grid.get(2, 0), Some(&'.'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.to_string(), text);
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        assert_eq!(
+            Grid::parse("...\n..\n...", |c| c),
+            Err(GridError::RaggedRow { row: 1, expected: 3, found: 2 })
+        );
+    }
+
+    #[test]
+    fn neighbors_stay_inside() {
+        let grid = Grid::new(3, 2, 0);
+        let mut corner = grid.neighbors4(0, 0).collect::<Vec<_>>();
+        corner.sort();
+        assert_eq!(corner, vec![(0, 1), (1, 0)]);
+
+        assert_eq!(grid.neighbors8(1, 0).count(), 5);
+        assert_eq!(grid.neighbors8(1, 1).count(), 5);
+        assert_eq!(grid.step(2, 1, (1, 0)), None);
+        assert_eq!(grid.step(2, 1, (-1, -1)), Some((1, 0)));
+    }
+}
@@ -0,0 +1,13 @@
+// Code shared between the days
+
+pub mod error;
+pub mod grid;
+pub mod parse;
+pub mod rng;
+pub mod solution;
+
+pub use error::{read_input, AocError};
+pub use grid::Grid;
+pub use parse::{ParseError, Span};
+pub use rng::Rng;
+pub use solution::{Answer, Options, Runner, Solution};
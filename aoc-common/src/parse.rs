@@ -0,0 +1,179 @@
+use std::fmt;
+use std::str::FromStr;
+
+// Helpers for reading puzzle input while keeping track of where each piece came from, so
+// a bad input can be reported as file:line:column instead of a bare panic. Lines and
+// columns are counted from 1, columns in characters.
+
+/** Structs */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+// Piece of the input together with its position
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span<'a> {
+    pub line: usize,
+    pub column: usize,
+    pub text: &'a str,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError { file: None, line, column, message: message.into() }
+    }
+
+    // Name the file the input was read from
+    pub fn in_file(self, file: impl Into<String>) -> ParseError {
+        ParseError { file: Some(file.into()), ..self }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}: {}", file, self.line, self.column, self.message),
+            None => write!(f, "line {}, column {}: {}", self.line, self.column, self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/** Functions */
+
+impl<'a> Span<'a> {
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.line, self.column, message)
+    }
+
+    // Part of this span starting at byte `start`, with its column moved along
+    fn sub(&self, start: usize, text: &'a str) -> Span<'a> {
+        Span { line: self.line, column: self.column + self.text[..start].chars().count(), text }
+    }
+
+    fn offset_of(&self, part: &str) -> usize {
+        part.as_ptr() as usize - self.text.as_ptr() as usize
+    }
+
+    // Whitespace separated words, however much whitespace there is between them
+    pub fn words(&self) -> Vec<Span<'a>> {
+        self.text.split_whitespace().map(|w| self.sub(self.offset_of(w), w)).collect()
+    }
+
+    // Fields between separators, each trimmed of surrounding whitespace
+    pub fn split(&self, separator: char) -> Vec<Span<'a>> {
+        self.text
+            .split(separator)
+            .map(|field| {
+                let trimmed = field.trim();
+                let start = self.offset_of(field) + (field.len() - field.trim_start().len());
+                self.sub(start, trimmed)
+            })
+            .collect()
+    }
+
+    // The whole span as a value, e.g. a number
+    pub fn parse<T: FromStr>(&self) -> Result<T, ParseError> {
+        self.text
+            .parse::<T>()
+            .map_err(|_| self.error(format!("invalid {} '{}'", short_type_name::<T>(), self.text)))
+    }
+
+    // Every integer in the span, skipping whatever stands between them
+    pub fn integers<T: FromStr>(&self) -> Result<Vec<T>, ParseError> {
+        let bytes = self.text.as_bytes();
+        let mut values = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            let negative = bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+            if !negative && !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            values.push(self.sub(start, &self.text[start..i]).parse()?);
+        }
+        Ok(values)
+    }
+}
+
+// "u64" rather than the full path for primitives, enough for an error message
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+// Every line that is not blank, without trailing whitespace or carriage returns
+pub fn lines(text: &str) -> Vec<Span<'_>> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| Span { line: i + 1, column: 1, text: line.trim_end() })
+        .filter(|span| !span.text.is_empty())
+        .collect()
+}
+
+// Groups of lines separated by one or more blank lines
+pub fn sections(text: &str) -> Vec<Vec<Span<'_>>> {
+    let mut sections = Vec::new();
+    let mut current = Vec::new();
+    let mut last_line = 0;
+
+    for line in lines(text) {
+        if line.line > last_line + 1 && !current.is_empty() {
+            sections.push(std::mem::take(&mut current));
+        }
+        last_line = line.line;
+        current.push(line);
+    }
+    if !current.is_empty() {
+        sections.push(current);
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_and_fields_keep_their_columns() {
+        let text = "\n  R   6 (#70c710)\r\n\n19, 13,30 @ -2,  1, -2\n";
+        let lines = lines(text);
+        assert_eq!(lines.len(), 2);
+
+        let words = lines[0].words();
+        assert_eq!(words.iter().map(|w| (w.text, w.line, w.column)).collect::<Vec<_>>(),
+            vec![("R", 2, 3), ("6", 2, 7), ("(#70c710)", 2, 9)]);
+
+        let velocity = lines[1].split('@')[1].split(',');
+        assert_eq!(velocity.iter().map(|w| (w.text, w.column)).collect::<Vec<_>>(),
+            vec![("-2", 13), ("1", 18), ("-2", 21)]);
+        assert_eq!(velocity[0].parse::<i64>(), Ok(-2));
+    }
+
+    #[test]
+    fn integers_and_their_errors() {
+        let line = lines("seeds: 79 14 -55 13")[0];
+        assert_eq!(line.integers::<i64>(), Ok(vec![79, 14, -55, 13]));
+
+        let error = lines("a\nmap: 12 300 7")[1].integers::<u8>().unwrap_err().in_file("input.txt");
+        assert_eq!(error.to_string(), "input.txt:2:9: invalid u8 '300'");
+    }
+
+    #[test]
+    fn sections_split_on_blank_lines() {
+        let sections = sections("seeds: 1 2\n\n\na map:\n1 2 3\n  \nb map:\n4 5 6\n");
+        let lines = sections.iter().map(|s| s.iter().map(|l| l.line).collect::<Vec<_>>()).collect::<Vec<_>>();
+        assert_eq!(lines, vec![vec![1], vec![4, 5], vec![7, 8]]);
+    }
+}
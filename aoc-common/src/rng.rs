@@ -0,0 +1,67 @@
+// Small xorshift generator for synthetic inputs. Its statistics are nothing special, but a
+// seed gives the same sequence on every platform and run, which is all benchmarks and
+// stress tests need.
+
+/** Structs */
+
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+/** Functions */
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // Zero is a fixed point of xorshift, so mix the seed with a constant first
+        let state = seed ^ 0x9e37_79b9_7f4a_7c15;
+        Rng { state: if state == 0 { 1 } else { state } }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    // Uniform-ish value in 0..n
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "Empty range");
+        self.next_u64() % n
+    }
+
+    // Value in lo..hi
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo < hi, "Empty range {}..{}", lo, hi);
+        lo + self.below((hi - lo) as u64) as i64
+    }
+
+    // True with probability p
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    // Fisher-Yates shuffle in place
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let (mut a, mut b) = (Rng::new(0), Rng::new(0));
+        for _ in 0..100 {
+            let value = a.range(-5, 5);
+            assert_eq!(value, b.range(-5, 5));
+            assert!((-5..5).contains(&value));
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::error::AocError;
+
+// A day split into its stages. The input is parsed once and both parts work on the
+// parsed form, so neither part has to read or parse the text again.
+pub trait Solution {
+    const DAY: u8;
+
+    // Input used when none is given on the command line
+    const DEFAULT_INPUT: &'static str;
+
+    // Option keys the day understands, the runner rejects any other
+    const OPTIONS: &'static [&'static str] = &[];
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError>;
+
+    // Parse with the options given for this run. Days without options only implement parse.
+    fn parse_with(input: &str, _options: &Options) -> Result<Self::Input, AocError> {
+        Self::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError>;
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError>;
+}
+
+/** Structs */
+
+// Settings a day needs besides its input text, such as the test area of day 24, given as
+// key=value pairs on the command line or next to the answers in answers.toml
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Options {
+    values: BTreeMap<String, String>,
+}
+
+// Puzzle answers are nearly always integers, but their types differ between days
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Answer {
+                Answer::Number(n as i128)
+            }
+        })*
+    };
+}
+
+answer_from_integer!(i32, i64, i128, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+// Answer to one part and how long solving it took
+#[derive(Debug, Clone)]
+pub struct PartRun {
+    pub part: u8,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+/** Functions */
+
+impl Options {
+    pub fn new() -> Options {
+        Options::default()
+    }
+
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.values.insert(key.into(), value.into());
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    // Only the options whose key is in `keys`
+    pub fn only(&self, keys: &[&str]) -> Options {
+        let values = self.values.iter().filter(|(k, _)| keys.contains(&k.as_str()));
+        Options { values: values.map(|(k, v)| (k.clone(), v.clone())).collect() }
+    }
+}
+
+// Object safe view of a Solution, so days with different Input types can share one registry
+pub trait Runner: Sync {
+    fn day(&self) -> u8;
+
+    fn default_input(&self) -> &'static str;
+
+    fn options(&self) -> &'static [&'static str];
+
+    // Parse once, then solve the requested parts (1 and/or 2) in order
+    fn run(&self, input: &str, parts: &[u8], options: &Options) -> Result<Run, AocError>;
+}
+
+impl<S: Solution + Sync> Runner for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn default_input(&self) -> &'static str {
+        S::DEFAULT_INPUT
+    }
+
+    fn options(&self) -> &'static [&'static str] {
+        S::OPTIONS
+    }
+
+    fn run(&self, input: &str, parts: &[u8], options: &Options) -> Result<Run, AocError> {
+        if let Some(key) = options.keys().find(|k| !S::OPTIONS.contains(k)) {
+            return Err(AocError::InvalidInput(format!("day {} has no option '{}'", S::DAY, key)));
+        }
+
+        let start = Instant::now();
+        let parsed = S::parse_with(input, options)?;
+        let parse_elapsed = start.elapsed();
+
+        let parts = parts
+            .iter()
+            .map(|part| {
+                let start = Instant::now();
+                let answer = match part {
+                    1 => S::part1(&parsed)?,
+                    2 => S::part2(&parsed)?,
+                    _ => return Err(AocError::InvalidInput(format!("day {} has no part {}", S::DAY, part))),
+                };
+                Ok(PartRun { part: *part, answer, elapsed: start.elapsed() })
+            })
+            .collect::<Result<Vec<_>, AocError>>()?;
+
+        Ok(Run { parse_elapsed, parts })
+    }
+}
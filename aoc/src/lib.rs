@@ -0,0 +1,17 @@
+use aoc_common::Runner;
+
+/** Statics */
+
+// Every day the runner knows about
+pub static SOLUTIONS: &[&dyn Runner] = &[
+    &rust_05::Day05,
+    &rust_16::Day16,
+    &rust_18::Day18,
+    &rust_24::Day24,
+];
+
+/** Functions */
+
+pub fn find(day: u8) -> Option<&'static dyn Runner> {
+    SOLUTIONS.iter().copied().find(|s| s.day() == day)
+}
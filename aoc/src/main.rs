@@ -0,0 +1,244 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use clap::builder::RangedU64ValueParser;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+
+use aoc::{find, SOLUTIONS};
+use aoc_common::{read_input, Answer, AocError, Options, Rng, Runner};
+
+/** Structs */
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    /// One line for parsing and one per part
+    Text,
+    /// One JSON object per part and line, for tools to read
+    Json,
+}
+
+// A line of --format json output
+#[derive(Serialize)]
+struct PartRecord<'a> {
+    day: u8,
+    part: u8,
+    answer: JsonAnswer<'a>,
+    parse_ms: f64,
+    solve_ms: f64,
+}
+
+// Numbers stay JSON numbers, even past the range of i64
+#[derive(Serialize)]
+#[serde(untagged)]
+enum JsonAnswer<'a> {
+    Number(i128),
+    Text(&'a str),
+}
+
+/** Functions */
+
+fn milliseconds(elapsed: Duration) -> f64 {
+    elapsed.as_secs_f64() * 1000.0
+}
+
+// "key=value" of --option
+fn parse_option(text: &str) -> Result<(String, String), String> {
+    text.split_once('=')
+        .map(|(key, value)| (String::from(key.trim()), String::from(value.trim())))
+        .ok_or_else(|| format!("expected key=value, found '{}'", text))
+}
+
+fn run(
+    solution: &dyn Runner,
+    part: Option<u8>,
+    input: Option<&PathBuf>,
+    options: &Options,
+    format: Format,
+) -> Result<(), AocError> {
+    let path = input.cloned().unwrap_or_else(|| PathBuf::from(solution.default_input()));
+    let text = read_input(&path)?;
+
+    let parts = match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+    let run = solution.run(&text, &parts, options).map_err(|e| e.in_file(path.display().to_string()))?;
+
+    match format {
+        Format::Text => {
+            println!("Day {} parsed in {:.3} ms", solution.day(), milliseconds(run.parse_elapsed));
+            for p in run.parts {
+                println!("Day {} part {}: {} ({:.3} ms)", solution.day(), p.part, p.answer, milliseconds(p.elapsed));
+            }
+        },
+        Format::Json => {
+            for p in &run.parts {
+                let record = PartRecord {
+                    day: solution.day(),
+                    part: p.part,
+                    answer: match &p.answer {
+                        Answer::Number(n) => JsonAnswer::Number(*n),
+                        Answer::Text(s) => JsonAnswer::Text(s),
+                    },
+                    parse_ms: milliseconds(run.parse_elapsed),
+                    solve_ms: milliseconds(p.elapsed),
+                };
+                println!("{}", serde_json::to_string(&record).expect("Answers always serialize"));
+            }
+        },
+    }
+    Ok(())
+}
+
+fn to_options(pairs: Vec<(String, String)>) -> Options {
+    let mut options = Options::new();
+    for (key, value) in pairs {
+        options.set(key, value);
+    }
+    options
+}
+
+// Input text for the generator, reporting known answers on stderr so stdout stays a clean input
+fn generate(generator: &Generator, rng: &mut Rng) -> String {
+    match generator {
+        Generator::Almanac { seeds, maps, entries } => rust_05::generate::almanac(rng, *seeds, *maps, *entries),
+        Generator::Contraption { width, height, density } => {
+            rust_16::generate::contraption(rng, *width, *height, *density)
+        },
+        Generator::DigPlan { columns, extent } => rust_18::generate::dig_plan(rng, *columns, *extent),
+        Generator::Hailstones { count } => {
+            let storm = rust_24::generate::hailstorm(rng, *count);
+            eprintln!("Day 24 part 1: {}", storm.part1());
+            eprintln!("Day 24 part 2: {}", storm.part2());
+            storm.text()
+        },
+    }
+}
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a day, or every registered day when --day is left out
+    Run {
+        #[arg(long)]
+        day: Option<u8>,
+
+        /// Part to run, both when left out
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Puzzle input, defaults to the day's own input.txt. Only one day can read it.
+        #[arg(long, requires = "day")]
+        input: Option<PathBuf>,
+
+        /// Day specific setting, e.g. test_area=7..27 for the day 24 example (repeatable)
+        #[arg(long = "option", value_name = "KEY=VALUE", value_parser = parse_option)]
+        options: Vec<(String, String)>,
+
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// List the registered days
+    List,
+    /// Write a random but valid puzzle input
+    Gen {
+        #[command(subcommand)]
+        generator: Generator,
+
+        /// The same seed always gives the same input
+        #[arg(long, global = true, default_value_t = 2023)]
+        seed: u64,
+
+        /// File to write, stdout when left out
+        #[arg(long, global = true)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum Generator {
+    /// Day 5 almanac of seed ranges and maps with disjoint entries
+    Almanac {
+        /// Number of seed ranges
+        #[arg(long, default_value_t = 10)]
+        seeds: usize,
+
+        #[arg(long, default_value_t = 7, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        maps: usize,
+
+        /// Entries per map, each in its own slot of the 2^32 keys
+        #[arg(long, default_value_t = 30, value_parser = RangedU64ValueParser::<usize>::new().range(1..=1 << 20))]
+        entries: usize,
+    },
+    /// Day 16 contraption of mirrors and splitters
+    Contraption {
+        #[arg(long, default_value_t = 110, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        width: usize,
+
+        #[arg(long, default_value_t = 110, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        height: usize,
+
+        /// Share of tiles holding a mirror or splitter
+        #[arg(long, default_value_t = 0.1)]
+        density: f64,
+    },
+    /// Day 18 closed dig plan that never crosses itself
+    DigPlan {
+        /// Columns in the band traced by the trench, the plan has four moves per column
+        #[arg(long, default_value_t = 170, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        columns: usize,
+
+        /// Longest part 1 edge, doubled for edges joining the bottom and the top of the band
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(i64).range(2..))]
+        extent: i64,
+    },
+    /// Day 24 hailstones all hit by one rock with designed crossings, printing both answers to stderr
+    Hailstones {
+        #[arg(long, default_value_t = 300)]
+        count: usize,
+    },
+}
+
+fn main() -> Result<(), AocError> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run { day: Some(day), part, input, options, format } => match find(day) {
+            Some(solution) => run(solution, part, input.as_ref(), &to_options(options), format)?,
+            None => return Err(AocError::NoSolution(format!("day {} is not registered", day))),
+        },
+        Command::Run { day: None, part, options, format, .. } => {
+            // Every day gets the options it knows, as long as some day knows each of them
+            let options = to_options(options);
+            if let Some(key) = options.keys().find(|k| SOLUTIONS.iter().all(|s| !s.options().contains(k))) {
+                return Err(AocError::InvalidInput(format!("no day has an option '{}'", key)));
+            }
+            for solution in SOLUTIONS {
+                run(*solution, part, None, &options.only(solution.options()), format)?;
+            }
+        },
+        Command::List => {
+            for solution in SOLUTIONS {
+                match solution.options() {
+                    [] => println!("Day {}: {}", solution.day(), solution.default_input()),
+                    keys => println!("Day {}: {} (options: {})", solution.day(), solution.default_input(), keys.join(", ")),
+                }
+            }
+        },
+        Command::Gen { generator, seed, output } => {
+            let text = generate(&generator, &mut Rng::new(seed));
+            match output {
+                Some(path) => fs::write(&path, text).map_err(|e| AocError::io(path.display(), e))?,
+                None => println!("{}", text),
+            }
+        },
+    }
+    Ok(())
+}
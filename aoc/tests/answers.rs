@@ -0,0 +1,114 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc::{find, SOLUTIONS};
+use aoc_common::Options;
+
+// Runs every registered day against the answers recorded in answers.toml at the repository
+// root, so a refactoring that changes any answer fails here instead of going unnoticed.
+
+/** Structs */
+
+struct Expected {
+    day: u8,
+    input: PathBuf,
+    options: Options,
+    answers: Vec<(u8, String)>,
+}
+
+/** Functions */
+
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().expect("aoc lives inside the workspace")
+}
+
+// Answers may be written as integers or, for text answers, as strings
+fn answer_text(value: &toml::Value) -> String {
+    match value {
+        toml::Value::Integer(n) => n.to_string(),
+        toml::Value::String(s) => s.clone(),
+        other => panic!("Unsupported answer {}", other),
+    }
+}
+
+fn load_expected() -> Vec<Expected> {
+    let text = fs::read_to_string(root().join("answers.toml")).expect("Unable to read answers.toml");
+    let table = text.parse::<toml::Table>().expect("answers.toml is not valid TOML");
+
+    table["answer"]
+        .as_array()
+        .expect("answers.toml needs [[answer]] entries")
+        .iter()
+        .map(|entry| {
+            let day = entry["day"].as_integer().expect("day must be an integer");
+            let input = entry["input"].as_str().expect("input must be a path");
+            let answers = [1, 2]
+                .into_iter()
+                .filter_map(|part| entry.get(format!("part{}", part)).map(|v| (part, answer_text(v))))
+                .collect();
+            let mut options = Options::new();
+            if let Some(table) = entry.get("options") {
+                for (key, value) in table.as_table().expect("options must be a table") {
+                    options.set(key.as_str(), value.as_str().expect("option values must be strings"));
+                }
+            }
+            Expected { day: day as u8, input: root().join(input), options, answers }
+        })
+        .collect()
+}
+
+#[test]
+fn every_day_has_recorded_answers() {
+    let expected = load_expected();
+    for solution in SOLUTIONS {
+        for file in ["example.txt", "input.txt"] {
+            assert!(
+                expected.iter().any(|e| e.day == solution.day() && e.input.ends_with(file)),
+                "No answers recorded for day {} {}",
+                solution.day(),
+                file
+            );
+        }
+    }
+}
+
+#[test]
+fn answers_match_recorded_ones() {
+    let mut mismatches = Vec::new();
+
+    for expected in load_expected() {
+        let solution = find(expected.day).unwrap_or_else(|| panic!("No solution registered for day {}", expected.day));
+        let text = fs::read_to_string(&expected.input)
+            .unwrap_or_else(|e| panic!("{}: {}", expected.input.display(), e));
+        let parts = expected.answers.iter().map(|(part, _)| *part).collect::<Vec<_>>();
+        let run = solution
+            .run(&text, &parts, &expected.options)
+            .unwrap_or_else(|e| panic!("{}: {}", expected.input.display(), e));
+
+        for (found, (part, answer)) in run.parts.iter().zip(&expected.answers) {
+            if found.answer.to_string() != *answer {
+                mismatches.push(format!(
+                    "day {} part {} on {}: expected {}, got {}",
+                    expected.day,
+                    part,
+                    expected.input.display(),
+                    answer,
+                    found.answer
+                ));
+            }
+        }
+    }
+
+    assert!(mismatches.is_empty(), "Answers changed:\n{}", mismatches.join("\n"));
+}
+
+// Input or parts no day can solve come back as errors instead of panics
+#[test]
+fn empty_input_and_unknown_parts_are_errors() {
+    for solution in SOLUTIONS {
+        let options = Options::new();
+        assert!(solution.run("", &[1, 2], &options).is_err(), "day {} accepted empty input", solution.day());
+        let input = fs::read_to_string(solution.default_input()).expect("Unable to read the default input");
+        assert!(solution.run(&input, &[3], &options).is_err(), "day {} solved part 3", solution.day());
+    }
+}